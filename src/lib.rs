@@ -0,0 +1,32 @@
+//! The liturgical calendar used by Church Date Updater.
+//!
+//! `lib_church_date` finds the church date (and its color and season) for any
+//! day, `lib_easter` calculates Easter, `lib_export` writes church dates as
+//! iCalendar, CSV or JSON, `lib_template` fills in title templates,
+//! `lib_sermon_title` checks sermon titles for common mistakes and `lib_error`
+//! is everything that can go wrong. Integrations are behind cargo features
+//! so tools that only need the calendar don't have to build them:
+//! - `youtube`: `lib_youtube_title`, updating the title of a YouTube live stream.
+
+pub mod lib_church_date;
+pub mod lib_easter;
+pub mod lib_error;
+pub mod lib_export;
+pub mod lib_sermon_title;
+pub mod lib_template;
+
+// `TitleChange` is kept in the history without the `youtube` feature too
+#[cfg(any(feature = "youtube", feature = "cli"))]
+pub mod lib_youtube_title;
+
+pub use lib_church_date::{
+    abbreviated_church_date, church_dates_between, date_ordinal, lectionary_year, next_church_date,
+    ChurchDateResult, Color, LectionaryYear, OrdinalLength, Season, Tradition,
+};
+pub use lib_easter::{gregorian_easter, julian_easter, EasterError};
+pub use lib_error::Error;
+pub use lib_export::{export, ExportFormat};
+pub use lib_sermon_title::{
+    check_sermon_title, corrected_sermon_title, quoted_sermon_title, SermonTitleProblem,
+};
+pub use lib_template::{Template, TemplateError, TemplateValue};
@@ -0,0 +1,158 @@
+use chrono::{Datelike, Duration, NaiveDate};
+use colored::{ColoredString, Colorize};
+
+use church_date_updater::lib_church_date::OrdinalLength::Short;
+use church_date_updater::lib_church_date::{
+    church_dates_between, ChurchDateResult, Color, Tradition,
+};
+use church_date_updater::lib_error::Error;
+
+// width of one month: 7 days, 2 characters each, 1 space between them
+const MONTH_WIDTH: usize = 20;
+
+fn paint(text: &str, color: Color) -> ColoredString {
+    match color {
+        Color::White => text.white(),
+        Color::Green => text.green(),
+        Color::Purple => text.purple(),
+        Color::Red => text.red(),
+        Color::Yellow => text.yellow(),
+        Color::Blue => text.blue(),
+        Color::Black => text.bright_black(), //some terminals will display black as background color
+    }
+}
+
+fn last_day_of_month(year: i32, month: u32) -> NaiveDate {
+    let (next_year, next_month) = if month == 12 {
+        (year + 1, 1)
+    } else {
+        (year, month + 1)
+    };
+    NaiveDate::from_ymd_opt(next_year, next_month, 1).unwrap() - Duration::days(1)
+}
+
+// every line is exactly MONTH_WIDTH characters wide (not counting color codes)
+// so months can be placed next to each other
+fn month_lines(
+    year: i32,
+    month: u32,
+    church_dates: &[ChurchDateResult],
+    show_year: bool,
+) -> Vec<String> {
+    let first_day = NaiveDate::from_ymd_opt(year, month, 1).unwrap();
+    let last_day = last_day_of_month(year, month);
+
+    let mut lines = Vec::new();
+
+    let title = if show_year {
+        first_day.format("%B %Y").to_string()
+    } else {
+        first_day.format("%B").to_string()
+    };
+    lines.push(format!("{title:^MONTH_WIDTH$}"));
+    lines.push("Su Mo Tu We Th Fr Sa".to_string());
+
+    let mut week = "   ".repeat(first_day.weekday().num_days_from_sunday() as usize);
+    let mut day = first_day;
+    while day <= last_day {
+        let number = format!("{:>2}", day.day());
+        match church_dates
+            .iter()
+            .find(|church_date| church_date.date == day)
+        {
+            Some(church_date) => {
+                week.push_str(&paint(&number, church_date.color).bold().to_string())
+            }
+            None => week.push_str(&number),
+        }
+
+        if day.weekday().num_days_from_sunday() == 6 || day == last_day {
+            // pad using the visible width, the color codes don't take up space
+            let visible_width = (day.weekday().num_days_from_sunday() as usize + 1) * 3 - 1;
+            week.push_str(&" ".repeat(MONTH_WIDTH - visible_width));
+            lines.push(week);
+            week = String::new();
+        } else {
+            week.push(' ');
+        }
+        day += Duration::days(1);
+    }
+
+    // always 6 weeks so months line up
+    while lines.len() < 8 {
+        lines.push(" ".repeat(MONTH_WIDTH));
+    }
+
+    lines
+}
+
+pub fn print_church_date(church_date: &ChurchDateResult) {
+    let date = church_date.date.format("%a %b %e %Y");
+    let color = church_date.color;
+    println!(
+        "{date}  {}  ({})",
+        paint(&church_date.text, color),
+        paint(&color.to_string(), color)
+    );
+}
+
+fn print_legend(church_dates: &[ChurchDateResult]) {
+    let mut season = None;
+    for church_date in church_dates {
+        if season != Some(church_date.season) {
+            season = Some(church_date.season);
+            println!();
+            println!("{}", church_date.season.to_string().bold());
+        }
+        print_church_date(church_date);
+    }
+}
+
+pub fn print_month(year: i32, month: u32, tradition: Tradition) -> Result<(), Error> {
+    let first_day = NaiveDate::from_ymd_opt(year, month, 1).unwrap();
+    let church_dates =
+        church_dates_between(first_day, last_day_of_month(year, month), Short, tradition)?;
+
+    for line in month_lines(year, month, &church_dates, true) {
+        println!("{}", line.trim_end());
+    }
+    print_legend(&church_dates);
+    Ok(())
+}
+
+pub fn print_year(year: i32, tradition: Tradition) -> Result<(), Error> {
+    let first_day = NaiveDate::from_ymd_opt(year, 1, 1).unwrap();
+    let last_day = NaiveDate::from_ymd_opt(year, 12, 31).unwrap();
+    let church_dates = church_dates_between(first_day, last_day, Short, tradition)?;
+
+    println!("{year:^64}");
+    println!();
+    // three months next to each other, like `cal -y`
+    for first_month in [1, 4, 7, 10] {
+        let months: Vec<Vec<String>> = (first_month..first_month + 3)
+            .map(|month| month_lines(year, month, &church_dates, false))
+            .collect();
+        for row in 0..months[0].len() {
+            let line = months
+                .iter()
+                .map(|month| month[row].as_str())
+                .collect::<Vec<&str>>()
+                .join("  ");
+            println!("{}", line.trim_end());
+        }
+        println!();
+    }
+    print_legend(&church_dates);
+    Ok(())
+}
+
+// only the list of church dates, for ranges that don't fit in a month or year
+pub fn print_range(start: NaiveDate, end: NaiveDate, tradition: Tradition) -> Result<(), Error> {
+    let church_dates = church_dates_between(start, end, Short, tradition)?;
+    if church_dates.is_empty() {
+        println!("There aren't any church dates from {start} to {end}.");
+    } else {
+        print_legend(&church_dates);
+    }
+    Ok(())
+}
@@ -1,5 +1,10 @@
 use chrono::{Datelike, Duration, NaiveDate, Weekday};
 use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::lib_easter::{gregorian_easter, EasterError};
+use crate::lib_error::Error;
 
 #[derive(Copy, Clone)]
 pub enum OrdinalLength {
@@ -56,6 +61,7 @@ pub fn date_ordinal(day: i64, length: OrdinalLength) -> String {
     }
 }
 
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
 pub enum Color {
     White,
     Green,
@@ -66,24 +72,210 @@ pub enum Color {
     Black,
 }
 
+impl fmt::Display for Color {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Color::White => "White",
+            Color::Green => "Green",
+            Color::Purple => "Purple",
+            Color::Red => "Red",
+            Color::Yellow => "Yellow",
+            Color::Blue => "Blue",
+            Color::Black => "Black",
+        };
+        write!(f, "{name}")
+    }
+}
+
+// the Time after Epiphany and the Time after Pentecost are named after the
+// festival that starts them, Holy Week is counted as part of Lent
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Season {
+    Advent,
+    Christmas,
+    Epiphany,
+    Lent,
+    Easter,
+    Pentecost,
+}
+
+impl fmt::Display for Season {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Season::Advent => "Advent",
+            Season::Christmas => "Christmas",
+            Season::Epiphany => "Epiphany",
+            Season::Lent => "Lent",
+            Season::Easter => "Easter",
+            Season::Pentecost => "Pentecost",
+        };
+        write!(f, "{name}")
+    }
+}
+
+// the three year cycle of readings in the Revised Common Lectionary
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum LectionaryYear {
+    A,
+    B,
+    C,
+}
+
+impl fmt::Display for LectionaryYear {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            LectionaryYear::A => "A",
+            LectionaryYear::B => "B",
+            LectionaryYear::C => "C",
+        };
+        write!(f, "{name}")
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
+pub enum Tradition {
+    // the ELCA calendar, with Reformation Sunday
+    #[default]
+    Lutheran,
+    // the Revised Common Lectionary without Lutheran festivals
+    Ecumenical,
+}
+
+impl FromStr for Tradition {
+    type Err = String;
+
+    fn from_str(tradition: &str) -> Result<Self, Self::Err> {
+        match tradition.to_lowercase().as_str() {
+            "lutheran" => Ok(Tradition::Lutheran),
+            "ecumenical" => Ok(Tradition::Ecumenical),
+            _ => Err(format!(
+                "'{tradition}' isn't a tradition, use lutheran or ecumenical"
+            )),
+        }
+    }
+}
+
+impl fmt::Display for Tradition {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Tradition::Lutheran => "lutheran",
+            Tradition::Ecumenical => "ecumenical",
+        };
+        write!(f, "{name}")
+    }
+}
+
 struct ChurchDate {
     church_text: String,
     liturgical_color: Color,
+    season: Season,
 }
 
-// in the current implementation date isn't used here but as a library I
-// would like it to still be included. This should only apply to one struct.
-#[allow(dead_code)]
+#[derive(Clone)]
 pub struct ChurchDateResult {
     pub date: NaiveDate,
     pub text: String,
     pub color: Color,
+    pub season: Season,
 }
 
-pub fn next_church_date(current_date: NaiveDate, length: OrdinalLength) -> ChurchDateResult {
+pub fn next_church_date(
+    current_date: NaiveDate,
+    length: OrdinalLength,
+    tradition: Tradition,
+) -> Result<ChurchDateResult, Error> {
+    let mut dates = church_dates(current_date.year(), length, tradition)?;
+
+    // after the last Sunday of Christmas the next date is in the next year
+    if dates.range(current_date..).next().is_none() {
+        dates = church_dates(current_date.year() + 1, length, tradition)?;
+    }
+
+    //find next date and return value:
+    dates
+        .into_iter()
+        .find(|(church_date, _)| *church_date >= current_date)
+        .map(|(church_date, church_value)| ChurchDateResult {
+            date: church_date,
+            text: church_value.church_text,
+            color: church_value.liturgical_color,
+            season: church_value.season,
+        })
+        .ok_or(Error::NoChurchDate(current_date))
+}
+
+// every church date from `start` to `end`, both inclusive
+pub fn church_dates_between(
+    start: NaiveDate,
+    end: NaiveDate,
+    length: OrdinalLength,
+    tradition: Tradition,
+) -> Result<Vec<ChurchDateResult>, Error> {
     let mut dates = BTreeMap::new();
 
-    let year = current_date.year();
+    // each year also generates the Sundays of Christmas from the end of the
+    // year before, so later years can safely overwrite earlier ones
+    for year in start.year()..=end.year() {
+        dates.extend(church_dates(year, length, tradition)?);
+    }
+
+    Ok(dates
+        .into_iter()
+        .filter(|(church_date, _)| *church_date >= start && *church_date <= end)
+        .map(|(church_date, church_value)| ChurchDateResult {
+            date: church_date,
+            text: church_value.church_text,
+            color: church_value.liturgical_color,
+            season: church_value.season,
+        })
+        .collect())
+}
+
+// "22nd Sunday after Pentecost" is "Pentecost 22" and "3rd Sunday in Lent" is
+// "Lent 3", other church dates stay the same
+pub fn abbreviated_church_date(text: &str) -> String {
+    let Some((ordinal, season)) = text.split_once(" Sunday ") else {
+        return text.to_string();
+    };
+    let number = ordinal.trim_end_matches(char::is_alphabetic);
+    let season = ["after ", "of ", "in "]
+        .iter()
+        .find_map(|preposition| season.strip_prefix(preposition));
+    match (number.parse::<u32>(), season) {
+        (Ok(number), Some(season)) => format!("{season} {number}"),
+        _ => text.to_string(),
+    }
+}
+
+// the church year starts on the 1st Sunday of Advent, which is between
+// November 27th and December 3rd, and Year A starts in years after a multiple of 3
+pub fn lectionary_year(date: NaiveDate) -> LectionaryYear {
+    let mut church_year = date.year();
+    if date.month() == 12 || (date.month() == 11 && date.day() >= 27) {
+        let advent_start = NaiveDate::from_ymd_opt(date.year(), 11, 27)
+            .expect("November 27th should be a valid date.");
+        let first_sunday_of_advent = advent_start
+            + Duration::days(i64::from(
+                (7 - advent_start.weekday().num_days_from_sunday()) % 7,
+            ));
+        if date >= first_sunday_of_advent {
+            church_year += 1;
+        }
+    }
+
+    match church_year.rem_euclid(3) {
+        1 => LectionaryYear::A,
+        2 => LectionaryYear::B,
+        _ => LectionaryYear::C,
+    }
+}
+
+fn church_dates(
+    year: i32,
+    length: OrdinalLength,
+    tradition: Tradition,
+) -> Result<BTreeMap<NaiveDate, ChurchDate>, EasterError> {
+    let mut dates = BTreeMap::new();
 
     //Beginning Sundays of Christmas*
     let mut beginning_sunday_christmas = NaiveDate::from_ymd_opt(year - 1, 12, 26).unwrap();
@@ -95,6 +287,7 @@ pub fn next_church_date(current_date: NaiveDate, length: OrdinalLength) -> Churc
         let beginning_sunday_christmas_value = ChurchDate {
             church_text: format!("{ordinal_week} Sunday of Christmas"),
             liturgical_color: Color::White,
+            season: Season::Christmas,
         };
         let beginning_sunday_christmas_date = beginning_sunday_christmas + Duration::weeks(week);
         dates.insert(
@@ -108,6 +301,7 @@ pub fn next_church_date(current_date: NaiveDate, length: OrdinalLength) -> Churc
     let epiphany_value = ChurchDate {
         church_text: "Epiphany of Our Lord".to_string(),
         liturgical_color: Color::White,
+        season: Season::Epiphany,
     };
     dates.insert(epiphany_date, epiphany_value);
 
@@ -119,6 +313,7 @@ pub fn next_church_date(current_date: NaiveDate, length: OrdinalLength) -> Churc
     let baptism_of_our_lord_value = ChurchDate {
         church_text: "Baptism of Our Lord".to_string(),
         liturgical_color: Color::White,
+        season: Season::Epiphany,
     };
     dates.insert(baptism_of_our_lord_date, baptism_of_our_lord_value);
 
@@ -128,20 +323,21 @@ pub fn next_church_date(current_date: NaiveDate, length: OrdinalLength) -> Churc
         let sunday_after_epiphany_value = ChurchDate {
             church_text: format!("{ordinal_week} Sunday after Epiphany"),
             liturgical_color: Color::Green,
+            season: Season::Epiphany,
         };
         let sunday_after_epiphany_date = baptism_of_our_lord_date + Duration::weeks(week);
         dates.insert(sunday_after_epiphany_date, sunday_after_epiphany_value);
     }
 
     //get easter
-    let easter_date = bdays::easter::easter_naive_date(year)
-        .expect("Should be a valid year for generating easter.");
+    let easter_date = gregorian_easter(year)?;
 
     //Transfiguration of Our Lord*
     let transfiguration_date = easter_date - Duration::weeks(7);
     let transfiguration_value = ChurchDate {
         church_text: "Transfiguration of Our Lord".to_string(),
         liturgical_color: Color::White,
+        season: Season::Epiphany,
     };
     dates.insert(transfiguration_date, transfiguration_value);
 
@@ -150,6 +346,7 @@ pub fn next_church_date(current_date: NaiveDate, length: OrdinalLength) -> Churc
     let ash_wednesday_value = ChurchDate {
         church_text: "Ash Wednesday".to_string(),
         liturgical_color: Color::Purple,
+        season: Season::Lent,
     };
     dates.insert(ash_wednesday_date, ash_wednesday_value);
 
@@ -160,6 +357,7 @@ pub fn next_church_date(current_date: NaiveDate, length: OrdinalLength) -> Churc
         let lent_sundays_value = ChurchDate {
             church_text: format!("{ordinal_week} Sunday in Lent"),
             liturgical_color: Color::Purple,
+            season: Season::Lent,
         };
         let lent_sundays_date = lent + Duration::weeks(week);
         dates.insert(lent_sundays_date, lent_sundays_value);
@@ -167,6 +365,7 @@ pub fn next_church_date(current_date: NaiveDate, length: OrdinalLength) -> Churc
         let lent_wednesdays_value = ChurchDate {
             church_text: format!("{ordinal_week} Wednesday in Lent"),
             liturgical_color: Color::Purple,
+            season: Season::Lent,
         };
         let lent_wednesdays_date = lent + Duration::weeks(week) + Duration::days(3);
         dates.insert(lent_wednesdays_date, lent_wednesdays_value);
@@ -177,6 +376,7 @@ pub fn next_church_date(current_date: NaiveDate, length: OrdinalLength) -> Churc
     let palm_sunday_value = ChurchDate {
         church_text: "Palm Sunday".to_string(),
         liturgical_color: Color::Purple,
+        season: Season::Lent,
     };
     dates.insert(palm_sunday_date, palm_sunday_value);
 
@@ -185,6 +385,7 @@ pub fn next_church_date(current_date: NaiveDate, length: OrdinalLength) -> Churc
     let maundy_thursday_value = ChurchDate {
         church_text: "Maundy Thursday".to_string(),
         liturgical_color: Color::Red,
+        season: Season::Lent,
     };
     dates.insert(maundy_thursday_date, maundy_thursday_value);
 
@@ -193,6 +394,7 @@ pub fn next_church_date(current_date: NaiveDate, length: OrdinalLength) -> Churc
     let good_friday_value = ChurchDate {
         church_text: "Good Friday".to_string(),
         liturgical_color: Color::Black,
+        season: Season::Lent,
     };
     dates.insert(good_friday_date, good_friday_value);
 
@@ -200,6 +402,7 @@ pub fn next_church_date(current_date: NaiveDate, length: OrdinalLength) -> Churc
     let easter_value = ChurchDate {
         church_text: "Easter Sunday".to_string(),
         liturgical_color: Color::Yellow,
+        season: Season::Easter,
     };
     dates.insert(easter_date, easter_value);
 
@@ -209,6 +412,7 @@ pub fn next_church_date(current_date: NaiveDate, length: OrdinalLength) -> Churc
         let easter_sundays_value = ChurchDate {
             church_text: format!("{ordinal_week} Sunday of Easter"),
             liturgical_color: Color::White,
+            season: Season::Easter,
         };
         let easter_sundays_date = easter_date + Duration::weeks(week);
         dates.insert(easter_sundays_date, easter_sundays_value);
@@ -219,6 +423,7 @@ pub fn next_church_date(current_date: NaiveDate, length: OrdinalLength) -> Churc
     let pentecost_value = ChurchDate {
         church_text: "Day of Pentecost".to_string(),
         liturgical_color: Color::Red,
+        season: Season::Easter,
     };
     dates.insert(pentecost_date, pentecost_value);
 
@@ -227,15 +432,17 @@ pub fn next_church_date(current_date: NaiveDate, length: OrdinalLength) -> Churc
     let holy_trinity_value = ChurchDate {
         church_text: "The Holy Trinity".to_string(),
         liturgical_color: Color::White,
+        season: Season::Pentecost,
     };
     dates.insert(holy_trinity_date, holy_trinity_value);
 
-    //Sundays after Pentecost*
-    for week in 9..=33 {
+    //Sundays after Pentecost* (an early Easter needs 27 before Christ the King)
+    for week in 9..=34 {
         let ordinal_week = date_ordinal(week - 7, length);
         let pentecost_sundays_value = ChurchDate {
             church_text: format!("{ordinal_week} Sunday after Pentecost"),
             liturgical_color: Color::Green,
+            season: Season::Pentecost,
         };
         let pentecost_sundays_date = easter_date + Duration::weeks(week);
         dates.insert(pentecost_sundays_date, pentecost_sundays_value);
@@ -250,6 +457,7 @@ pub fn next_church_date(current_date: NaiveDate, length: OrdinalLength) -> Churc
     let christ_the_king_value = ChurchDate {
         church_text: "Christ the King Sunday".to_string(),
         liturgical_color: Color::White,
+        season: Season::Pentecost,
     };
     dates.insert(christ_the_king_date, christ_the_king_value);
 
@@ -259,6 +467,7 @@ pub fn next_church_date(current_date: NaiveDate, length: OrdinalLength) -> Churc
         let advent_value = ChurchDate {
             church_text: format!("{ordinal_week} Sunday of Advent"),
             liturgical_color: Color::Blue,
+            season: Season::Advent,
         };
         let advent_date = christ_the_king_date + Duration::weeks(week);
         dates.insert(advent_date, advent_value);
@@ -269,6 +478,7 @@ pub fn next_church_date(current_date: NaiveDate, length: OrdinalLength) -> Churc
     let christmas_eve_value = ChurchDate {
         church_text: "Christmas Eve".to_string(),
         liturgical_color: Color::White,
+        season: Season::Christmas,
     };
     dates.insert(christmas_eve_date, christmas_eve_value);
 
@@ -277,6 +487,7 @@ pub fn next_church_date(current_date: NaiveDate, length: OrdinalLength) -> Churc
     let christmas_value = ChurchDate {
         church_text: "Christmas Day".to_string(),
         liturgical_color: Color::White,
+        season: Season::Christmas,
     };
     dates.insert(christmas_date, christmas_value);
 
@@ -288,6 +499,7 @@ pub fn next_church_date(current_date: NaiveDate, length: OrdinalLength) -> Churc
     let first_sunday_christmas_value = ChurchDate {
         church_text: format!("{} Sunday of Christmas", date_ordinal(1, length)),
         liturgical_color: Color::White,
+        season: Season::Christmas,
     };
     dates.insert(first_sunday_christmas_date, first_sunday_christmas_value);
 
@@ -301,32 +513,508 @@ pub fn next_church_date(current_date: NaiveDate, length: OrdinalLength) -> Churc
     let reformation_value = ChurchDate {
         church_text: "Reformation Sunday".to_string(),
         liturgical_color: Color::Red,
+        season: Season::Pentecost,
     };
-    dates.insert(reformation_date, reformation_value);
+    if tradition == Tradition::Lutheran {
+        dates.insert(reformation_date, reformation_value);
+    }
 
     //All Saints Sunday* (the Sunday on or after November 1st)
     let reformation_date = reformation_date + Duration::weeks(1);
     let reformation_value = ChurchDate {
         church_text: "All Saints Sunday".to_string(),
         liturgical_color: Color::White,
+        season: Season::Pentecost,
     };
     dates.insert(reformation_date, reformation_value);
 
     //Confirmation Sunday* (whenever it's convenient)
 
-    //find next date and return value:
-    let mut result = None;
-
-    for (church_date, church_value) in dates {
-        if church_date >= current_date {
-            result = Some(ChurchDateResult {
-                date: church_date,
-                text: church_value.church_text,
-                color: church_value.liturgical_color,
-            });
-            break;
+    Ok(dates)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Observances as published in Lutheran church calendars, chosen to cover
+    // the earliest and latest Easters of the range, and years where Christmas
+    // Eve or Christmas Day falls on a Sunday.
+    const GOLDEN: &[(&str, &str, Color)] = &[
+        // 1900: Easter April 15
+        ("1900-01-07", "Baptism of Our Lord", Color::White),
+        ("1900-02-25", "Transfiguration of Our Lord", Color::White),
+        ("1900-02-28", "Ash Wednesday", Color::Purple),
+        ("1900-04-08", "Palm Sunday", Color::Purple),
+        ("1900-04-13", "Good Friday", Color::Black),
+        ("1900-04-15", "Easter Sunday", Color::Yellow),
+        ("1900-06-03", "Day of Pentecost", Color::Red),
+        ("1900-06-10", "The Holy Trinity", Color::White),
+        ("1900-10-28", "Reformation Sunday", Color::Red),
+        ("1900-11-04", "All Saints Sunday", Color::White),
+        ("1900-11-18", "24th Sunday after Pentecost", Color::Green),
+        ("1900-11-25", "Christ the King Sunday", Color::White),
+        ("1900-12-02", "1st Sunday of Advent", Color::Blue),
+        ("1900-12-23", "4th Sunday of Advent", Color::Blue),
+        ("1900-12-24", "Christmas Eve", Color::White),
+        ("1900-12-25", "Christmas Day", Color::White),
+        ("1900-12-30", "1st Sunday of Christmas", Color::White),
+        // 1913: Easter March 23
+        ("1913-01-12", "Baptism of Our Lord", Color::White),
+        ("1913-02-02", "Transfiguration of Our Lord", Color::White),
+        ("1913-02-05", "Ash Wednesday", Color::Purple),
+        ("1913-03-16", "Palm Sunday", Color::Purple),
+        ("1913-03-21", "Good Friday", Color::Black),
+        ("1913-03-23", "Easter Sunday", Color::Yellow),
+        ("1913-05-11", "Day of Pentecost", Color::Red),
+        ("1913-05-18", "The Holy Trinity", Color::White),
+        ("1913-10-26", "Reformation Sunday", Color::Red),
+        ("1913-11-02", "All Saints Sunday", Color::White),
+        ("1913-11-16", "27th Sunday after Pentecost", Color::Green),
+        ("1913-11-23", "Christ the King Sunday", Color::White),
+        ("1913-11-30", "1st Sunday of Advent", Color::Blue),
+        ("1913-12-21", "4th Sunday of Advent", Color::Blue),
+        ("1913-12-24", "Christmas Eve", Color::White),
+        ("1913-12-25", "Christmas Day", Color::White),
+        ("1913-12-28", "1st Sunday of Christmas", Color::White),
+        // 1943: Easter April 25
+        ("1943-01-10", "Baptism of Our Lord", Color::White),
+        ("1943-03-07", "Transfiguration of Our Lord", Color::White),
+        ("1943-03-10", "Ash Wednesday", Color::Purple),
+        ("1943-04-18", "Palm Sunday", Color::Purple),
+        ("1943-04-23", "Good Friday", Color::Black),
+        ("1943-04-25", "Easter Sunday", Color::Yellow),
+        ("1943-06-13", "Day of Pentecost", Color::Red),
+        ("1943-06-20", "The Holy Trinity", Color::White),
+        ("1943-10-31", "Reformation Sunday", Color::Red),
+        ("1943-11-07", "All Saints Sunday", Color::White),
+        ("1943-11-14", "22nd Sunday after Pentecost", Color::Green),
+        ("1943-11-21", "Christ the King Sunday", Color::White),
+        ("1943-11-28", "1st Sunday of Advent", Color::Blue),
+        ("1943-12-19", "4th Sunday of Advent", Color::Blue),
+        ("1943-12-24", "Christmas Eve", Color::White),
+        ("1943-12-25", "Christmas Day", Color::White),
+        ("1943-12-26", "1st Sunday of Christmas", Color::White),
+        // 1950: Easter April 9
+        ("1950-01-08", "Baptism of Our Lord", Color::White),
+        ("1950-02-19", "Transfiguration of Our Lord", Color::White),
+        ("1950-02-22", "Ash Wednesday", Color::Purple),
+        ("1950-04-02", "Palm Sunday", Color::Purple),
+        ("1950-04-07", "Good Friday", Color::Black),
+        ("1950-04-09", "Easter Sunday", Color::Yellow),
+        ("1950-05-28", "Day of Pentecost", Color::Red),
+        ("1950-06-04", "The Holy Trinity", Color::White),
+        ("1950-10-29", "Reformation Sunday", Color::Red),
+        ("1950-11-05", "All Saints Sunday", Color::White),
+        ("1950-11-19", "25th Sunday after Pentecost", Color::Green),
+        ("1950-11-26", "Christ the King Sunday", Color::White),
+        ("1950-12-03", "1st Sunday of Advent", Color::Blue),
+        ("1950-12-24", "Christmas Eve", Color::White),
+        ("1950-12-25", "Christmas Day", Color::White),
+        ("1950-12-31", "1st Sunday of Christmas", Color::White),
+        // 2000: Easter April 23
+        ("2000-01-09", "Baptism of Our Lord", Color::White),
+        ("2000-03-05", "Transfiguration of Our Lord", Color::White),
+        ("2000-03-08", "Ash Wednesday", Color::Purple),
+        ("2000-04-16", "Palm Sunday", Color::Purple),
+        ("2000-04-21", "Good Friday", Color::Black),
+        ("2000-04-23", "Easter Sunday", Color::Yellow),
+        ("2000-06-11", "Day of Pentecost", Color::Red),
+        ("2000-06-18", "The Holy Trinity", Color::White),
+        ("2000-10-29", "Reformation Sunday", Color::Red),
+        ("2000-11-05", "All Saints Sunday", Color::White),
+        ("2000-11-19", "23rd Sunday after Pentecost", Color::Green),
+        ("2000-11-26", "Christ the King Sunday", Color::White),
+        ("2000-12-03", "1st Sunday of Advent", Color::Blue),
+        ("2000-12-24", "Christmas Eve", Color::White),
+        ("2000-12-25", "Christmas Day", Color::White),
+        ("2000-12-31", "1st Sunday of Christmas", Color::White),
+        // 2008: Easter March 23
+        ("2008-01-13", "Baptism of Our Lord", Color::White),
+        ("2008-02-03", "Transfiguration of Our Lord", Color::White),
+        ("2008-02-06", "Ash Wednesday", Color::Purple),
+        ("2008-03-16", "Palm Sunday", Color::Purple),
+        ("2008-03-21", "Good Friday", Color::Black),
+        ("2008-03-23", "Easter Sunday", Color::Yellow),
+        ("2008-05-11", "Day of Pentecost", Color::Red),
+        ("2008-05-18", "The Holy Trinity", Color::White),
+        ("2008-10-26", "Reformation Sunday", Color::Red),
+        ("2008-11-02", "All Saints Sunday", Color::White),
+        ("2008-11-16", "27th Sunday after Pentecost", Color::Green),
+        ("2008-11-23", "Christ the King Sunday", Color::White),
+        ("2008-11-30", "1st Sunday of Advent", Color::Blue),
+        ("2008-12-21", "4th Sunday of Advent", Color::Blue),
+        ("2008-12-24", "Christmas Eve", Color::White),
+        ("2008-12-25", "Christmas Day", Color::White),
+        ("2008-12-28", "1st Sunday of Christmas", Color::White),
+        // 2011: Easter April 24
+        ("2011-01-09", "Baptism of Our Lord", Color::White),
+        ("2011-03-06", "Transfiguration of Our Lord", Color::White),
+        ("2011-03-09", "Ash Wednesday", Color::Purple),
+        ("2011-04-17", "Palm Sunday", Color::Purple),
+        ("2011-04-22", "Good Friday", Color::Black),
+        ("2011-04-24", "Easter Sunday", Color::Yellow),
+        ("2011-06-12", "Day of Pentecost", Color::Red),
+        ("2011-06-19", "The Holy Trinity", Color::White),
+        ("2011-10-30", "Reformation Sunday", Color::Red),
+        ("2011-11-06", "All Saints Sunday", Color::White),
+        ("2011-11-13", "22nd Sunday after Pentecost", Color::Green),
+        ("2011-11-20", "Christ the King Sunday", Color::White),
+        ("2011-11-27", "1st Sunday of Advent", Color::Blue),
+        ("2011-12-18", "4th Sunday of Advent", Color::Blue),
+        ("2011-12-24", "Christmas Eve", Color::White),
+        ("2011-12-25", "Christmas Day", Color::White),
+        ("2012-01-01", "1st Sunday of Christmas", Color::White),
+        // 2017: Easter April 16
+        ("2017-01-08", "Baptism of Our Lord", Color::White),
+        ("2017-02-26", "Transfiguration of Our Lord", Color::White),
+        ("2017-03-01", "Ash Wednesday", Color::Purple),
+        ("2017-04-09", "Palm Sunday", Color::Purple),
+        ("2017-04-14", "Good Friday", Color::Black),
+        ("2017-04-16", "Easter Sunday", Color::Yellow),
+        ("2017-06-04", "Day of Pentecost", Color::Red),
+        ("2017-06-11", "The Holy Trinity", Color::White),
+        ("2017-10-29", "Reformation Sunday", Color::Red),
+        ("2017-11-05", "All Saints Sunday", Color::White),
+        ("2017-11-19", "24th Sunday after Pentecost", Color::Green),
+        ("2017-11-26", "Christ the King Sunday", Color::White),
+        ("2017-12-03", "1st Sunday of Advent", Color::Blue),
+        ("2017-12-24", "Christmas Eve", Color::White),
+        ("2017-12-25", "Christmas Day", Color::White),
+        ("2017-12-31", "1st Sunday of Christmas", Color::White),
+        // 2022: Easter April 17
+        ("2022-01-09", "Baptism of Our Lord", Color::White),
+        ("2022-02-27", "Transfiguration of Our Lord", Color::White),
+        ("2022-03-02", "Ash Wednesday", Color::Purple),
+        ("2022-04-10", "Palm Sunday", Color::Purple),
+        ("2022-04-15", "Good Friday", Color::Black),
+        ("2022-04-17", "Easter Sunday", Color::Yellow),
+        ("2022-06-05", "Day of Pentecost", Color::Red),
+        ("2022-06-12", "The Holy Trinity", Color::White),
+        ("2022-10-30", "Reformation Sunday", Color::Red),
+        ("2022-11-06", "All Saints Sunday", Color::White),
+        ("2022-11-13", "23rd Sunday after Pentecost", Color::Green),
+        ("2022-11-20", "Christ the King Sunday", Color::White),
+        ("2022-11-27", "1st Sunday of Advent", Color::Blue),
+        ("2022-12-18", "4th Sunday of Advent", Color::Blue),
+        ("2022-12-24", "Christmas Eve", Color::White),
+        ("2022-12-25", "Christmas Day", Color::White),
+        ("2023-01-01", "1st Sunday of Christmas", Color::White),
+        // 2023: Easter April 9
+        ("2023-01-08", "Baptism of Our Lord", Color::White),
+        ("2023-02-19", "Transfiguration of Our Lord", Color::White),
+        ("2023-02-22", "Ash Wednesday", Color::Purple),
+        ("2023-04-02", "Palm Sunday", Color::Purple),
+        ("2023-04-07", "Good Friday", Color::Black),
+        ("2023-04-09", "Easter Sunday", Color::Yellow),
+        ("2023-05-28", "Day of Pentecost", Color::Red),
+        ("2023-06-04", "The Holy Trinity", Color::White),
+        ("2023-10-29", "Reformation Sunday", Color::Red),
+        ("2023-11-05", "All Saints Sunday", Color::White),
+        ("2023-11-19", "25th Sunday after Pentecost", Color::Green),
+        ("2023-11-26", "Christ the King Sunday", Color::White),
+        ("2023-12-03", "1st Sunday of Advent", Color::Blue),
+        ("2023-12-24", "Christmas Eve", Color::White),
+        ("2023-12-25", "Christmas Day", Color::White),
+        ("2023-12-31", "1st Sunday of Christmas", Color::White),
+        // 2024: Easter March 31
+        ("2024-01-07", "Baptism of Our Lord", Color::White),
+        ("2024-02-11", "Transfiguration of Our Lord", Color::White),
+        ("2024-02-14", "Ash Wednesday", Color::Purple),
+        ("2024-03-24", "Palm Sunday", Color::Purple),
+        ("2024-03-29", "Good Friday", Color::Black),
+        ("2024-03-31", "Easter Sunday", Color::Yellow),
+        ("2024-05-19", "Day of Pentecost", Color::Red),
+        ("2024-05-26", "The Holy Trinity", Color::White),
+        ("2024-10-27", "Reformation Sunday", Color::Red),
+        ("2024-11-03", "All Saints Sunday", Color::White),
+        ("2024-11-17", "26th Sunday after Pentecost", Color::Green),
+        ("2024-11-24", "Christ the King Sunday", Color::White),
+        ("2024-12-01", "1st Sunday of Advent", Color::Blue),
+        ("2024-12-22", "4th Sunday of Advent", Color::Blue),
+        ("2024-12-24", "Christmas Eve", Color::White),
+        ("2024-12-25", "Christmas Day", Color::White),
+        ("2024-12-29", "1st Sunday of Christmas", Color::White),
+        // 2025: Easter April 20
+        ("2025-01-12", "Baptism of Our Lord", Color::White),
+        ("2025-03-02", "Transfiguration of Our Lord", Color::White),
+        ("2025-03-05", "Ash Wednesday", Color::Purple),
+        ("2025-04-13", "Palm Sunday", Color::Purple),
+        ("2025-04-18", "Good Friday", Color::Black),
+        ("2025-04-20", "Easter Sunday", Color::Yellow),
+        ("2025-06-08", "Day of Pentecost", Color::Red),
+        ("2025-06-15", "The Holy Trinity", Color::White),
+        ("2025-10-26", "Reformation Sunday", Color::Red),
+        ("2025-11-02", "All Saints Sunday", Color::White),
+        ("2025-11-16", "23rd Sunday after Pentecost", Color::Green),
+        ("2025-11-23", "Christ the King Sunday", Color::White),
+        ("2025-11-30", "1st Sunday of Advent", Color::Blue),
+        ("2025-12-21", "4th Sunday of Advent", Color::Blue),
+        ("2025-12-24", "Christmas Eve", Color::White),
+        ("2025-12-25", "Christmas Day", Color::White),
+        ("2025-12-28", "1st Sunday of Christmas", Color::White),
+        // 2038: Easter April 25
+        ("2038-01-10", "Baptism of Our Lord", Color::White),
+        ("2038-03-07", "Transfiguration of Our Lord", Color::White),
+        ("2038-03-10", "Ash Wednesday", Color::Purple),
+        ("2038-04-18", "Palm Sunday", Color::Purple),
+        ("2038-04-23", "Good Friday", Color::Black),
+        ("2038-04-25", "Easter Sunday", Color::Yellow),
+        ("2038-06-13", "Day of Pentecost", Color::Red),
+        ("2038-06-20", "The Holy Trinity", Color::White),
+        ("2038-10-31", "Reformation Sunday", Color::Red),
+        ("2038-11-07", "All Saints Sunday", Color::White),
+        ("2038-11-14", "22nd Sunday after Pentecost", Color::Green),
+        ("2038-11-21", "Christ the King Sunday", Color::White),
+        ("2038-11-28", "1st Sunday of Advent", Color::Blue),
+        ("2038-12-19", "4th Sunday of Advent", Color::Blue),
+        ("2038-12-24", "Christmas Eve", Color::White),
+        ("2038-12-25", "Christmas Day", Color::White),
+        ("2038-12-26", "1st Sunday of Christmas", Color::White),
+        // 2100: Easter March 28
+        ("2100-01-10", "Baptism of Our Lord", Color::White),
+        ("2100-02-07", "Transfiguration of Our Lord", Color::White),
+        ("2100-02-10", "Ash Wednesday", Color::Purple),
+        ("2100-03-21", "Palm Sunday", Color::Purple),
+        ("2100-03-26", "Good Friday", Color::Black),
+        ("2100-03-28", "Easter Sunday", Color::Yellow),
+        ("2100-05-16", "Day of Pentecost", Color::Red),
+        ("2100-05-23", "The Holy Trinity", Color::White),
+        ("2100-10-31", "Reformation Sunday", Color::Red),
+        ("2100-11-07", "All Saints Sunday", Color::White),
+        ("2100-11-14", "26th Sunday after Pentecost", Color::Green),
+        ("2100-11-21", "Christ the King Sunday", Color::White),
+        ("2100-11-28", "1st Sunday of Advent", Color::Blue),
+        ("2100-12-19", "4th Sunday of Advent", Color::Blue),
+        ("2100-12-24", "Christmas Eve", Color::White),
+        ("2100-12-25", "Christmas Day", Color::White),
+        ("2100-12-26", "1st Sunday of Christmas", Color::White),
+    ];
+
+    fn date(date: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(date, "%Y-%m-%d").unwrap()
+    }
+
+    fn all_days(first_year: i32, last_year: i32) -> impl Iterator<Item = NaiveDate> {
+        let first_day = NaiveDate::from_ymd_opt(first_year, 1, 1).unwrap();
+        let last_day = NaiveDate::from_ymd_opt(last_year, 12, 31).unwrap();
+        first_day
+            .iter_days()
+            .take_while(move |day| *day <= last_day)
+    }
+
+    #[test]
+    fn golden_observances() {
+        for (golden_date, text, color) in GOLDEN {
+            let golden_date = date(golden_date);
+            let result =
+                next_church_date(golden_date, OrdinalLength::Short, Tradition::Lutheran).unwrap();
+            assert_eq!(result.date, golden_date, "{text}");
+            assert_eq!(result.text, *text, "{golden_date}");
+            assert_eq!(result.color, *color, "{golden_date}");
+        }
+    }
+
+    #[test]
+    fn ecumenical_has_no_reformation_sunday() {
+        let result = next_church_date(
+            date("2026-10-25"),
+            OrdinalLength::Short,
+            Tradition::Ecumenical,
+        )
+        .unwrap();
+        assert_eq!(result.text, "22nd Sunday after Pentecost");
+        assert_eq!(result.color, Color::Green);
+    }
+
+    #[test]
+    fn lectionary_years() {
+        // Advent 2025 starts Year A
+        assert_eq!(lectionary_year(date("2025-11-29")), LectionaryYear::C);
+        assert_eq!(lectionary_year(date("2025-11-30")), LectionaryYear::A);
+        assert_eq!(lectionary_year(date("2026-10-25")), LectionaryYear::A);
+        assert_eq!(lectionary_year(date("2026-11-29")), LectionaryYear::B);
+        assert_eq!(lectionary_year(date("2027-12-25")), LectionaryYear::C);
+        // Advent on December 3rd
+        assert_eq!(lectionary_year(date("2023-12-02")), LectionaryYear::A);
+        assert_eq!(lectionary_year(date("2023-12-03")), LectionaryYear::B);
+    }
+
+    #[test]
+    fn abbreviations() {
+        assert_eq!(
+            abbreviated_church_date("22nd Sunday after Pentecost"),
+            "Pentecost 22"
+        );
+        assert_eq!(abbreviated_church_date("3rd Sunday in Lent"), "Lent 3");
+        assert_eq!(abbreviated_church_date("1st Sunday of Advent"), "Advent 1");
+        assert_eq!(
+            abbreviated_church_date("First Sunday of Advent"),
+            "First Sunday of Advent"
+        );
+        assert_eq!(
+            abbreviated_church_date("Reformation Sunday"),
+            "Reformation Sunday"
+        );
+    }
+
+    #[test]
+    fn long_ordinals() {
+        let result =
+            next_church_date(date("2024-12-01"), OrdinalLength::Long, Tradition::Lutheran).unwrap();
+        assert_eq!(result.text, "First Sunday of Advent");
+
+        let result =
+            next_church_date(date("2008-11-16"), OrdinalLength::Long, Tradition::Lutheran).unwrap();
+        assert_eq!(result.text, "Twenty-Seventh Sunday after Pentecost");
+    }
+
+    #[test]
+    fn earliest_possible_easter() {
+        // March 22nd doesn't happen between 1900 and 2100, the next one is in 2285
+        let easter = next_church_date(
+            date("2285-03-22"),
+            OrdinalLength::Short,
+            Tradition::Lutheran,
+        )
+        .unwrap();
+        assert_eq!(easter.date, date("2285-03-22"));
+        assert_eq!(easter.text, "Easter Sunday");
+
+        let transfiguration = next_church_date(
+            date("2285-02-01"),
+            OrdinalLength::Short,
+            Tradition::Lutheran,
+        )
+        .unwrap();
+        assert_eq!(transfiguration.date, date("2285-02-01"));
+        assert_eq!(transfiguration.text, "Transfiguration of Our Lord");
+
+        let ash_wednesday = next_church_date(
+            date("2285-02-02"),
+            OrdinalLength::Short,
+            Tradition::Lutheran,
+        )
+        .unwrap();
+        assert_eq!(ash_wednesday.date, date("2285-02-04"));
+        assert_eq!(ash_wednesday.text, "Ash Wednesday");
+    }
+
+    #[test]
+    fn christmas_eve_on_sunday_replaces_fourth_sunday_of_advent() {
+        let third_advent = next_church_date(
+            date("2023-12-17"),
+            OrdinalLength::Short,
+            Tradition::Lutheran,
+        )
+        .unwrap();
+        assert_eq!(third_advent.text, "3rd Sunday of Advent");
+
+        let christmas_eve = next_church_date(
+            date("2023-12-18"),
+            OrdinalLength::Short,
+            Tradition::Lutheran,
+        )
+        .unwrap();
+        assert_eq!(christmas_eve.date, date("2023-12-24"));
+        assert_eq!(christmas_eve.text, "Christmas Eve");
+    }
+
+    #[test]
+    fn out_of_range_year() {
+        assert!(next_church_date(
+            date("1582-06-01"),
+            OrdinalLength::Short,
+            Tradition::Lutheran
+        )
+        .is_err());
+        assert!(next_church_date(
+            date("9999-12-31"),
+            OrdinalLength::Short,
+            Tradition::Lutheran
+        )
+        .is_err());
+        assert!(next_church_date(
+            date("9999-12-01"),
+            OrdinalLength::Short,
+            Tradition::Lutheran
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn end_of_december_uses_next_year() {
+        let result = next_church_date(
+            date("1900-12-31"),
+            OrdinalLength::Short,
+            Tradition::Lutheran,
+        )
+        .unwrap();
+        assert_eq!(result.date, date("1901-01-06"));
+        assert_eq!(result.text, "Epiphany of Our Lord");
+    }
+
+    #[test]
+    fn every_sunday_has_one_observance() {
+        let first_day = NaiveDate::from_ymd_opt(1900, 1, 1).unwrap();
+        let last_day = NaiveDate::from_ymd_opt(2100, 12, 31).unwrap();
+        let church_dates = church_dates_between(
+            first_day,
+            last_day,
+            OrdinalLength::Short,
+            Tradition::Lutheran,
+        )
+        .unwrap();
+
+        let sundays: Vec<NaiveDate> = all_days(1900, 2100)
+            .filter(|day| day.weekday() == Weekday::Sun)
+            .collect();
+        let church_sundays: Vec<NaiveDate> = church_dates
+            .iter()
+            .map(|church_date| church_date.date)
+            .filter(|day| day.weekday() == Weekday::Sun)
+            .collect();
+        assert_eq!(sundays, church_sundays);
+    }
+
+    #[test]
+    fn next_church_date_is_within_a_week() {
+        for day in all_days(1900, 2100) {
+            let result = next_church_date(day, OrdinalLength::Short, Tradition::Lutheran).unwrap();
+            assert!(result.date >= day, "{day}");
+            assert!(result.date - day < Duration::weeks(1), "{day}");
         }
     }
 
-    result.expect("Current date should be within generated dates.")
+    #[test]
+    fn seasons_are_contiguous() {
+        let order = [
+            Season::Advent,
+            Season::Christmas,
+            Season::Epiphany,
+            Season::Lent,
+            Season::Easter,
+            Season::Pentecost,
+        ];
+
+        let first_day = NaiveDate::from_ymd_opt(1900, 1, 1).unwrap();
+        let last_day = NaiveDate::from_ymd_opt(2100, 12, 31).unwrap();
+        let church_dates = church_dates_between(
+            first_day,
+            last_day,
+            OrdinalLength::Short,
+            Tradition::Lutheran,
+        )
+        .unwrap();
+
+        let mut previous = &church_dates[0];
+        for church_date in &church_dates[1..] {
+            if church_date.season != previous.season {
+                let position = order.iter().position(|season| *season == previous.season);
+                let expected = order[(position.unwrap() + 1) % order.len()];
+                assert_eq!(church_date.season, expected, "{}", church_date.date);
+            }
+            previous = church_date;
+        }
+    }
 }
@@ -0,0 +1,140 @@
+use chrono::NaiveDate;
+use clap::builder::PossibleValuesParser;
+use clap::{Parser, Subcommand};
+use std::path::PathBuf;
+
+use church_date_updater::lib_export::ExportFormat;
+
+// same names as the images in `pics/`
+pub const COLOR_NAMES: [&str; 8] = [
+    "White", "Green", "Purple", "Red", "Yellow", "Blue", "Black", "None",
+];
+
+/// Generate liturgical dates and colors for OBS and YouTube.
+///
+/// Without a command the setup is run, asking every question that wasn't
+/// answered with an option.
+#[derive(Parser)]
+#[command(version, args_conflicts_with_subcommands = true)]
+pub struct Args {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
+    #[command(flatten)]
+    pub publish: PublishArgs,
+
+    /// Use the settings of a profile from the settings file, like `--profile chapel`
+    #[arg(long, global = true)]
+    pub profile: Option<String>,
+}
+
+// each option answers one of the setup questions, so the program can also run
+// from a scheduled task or a Stream Deck button
+#[derive(clap::Args)]
+pub struct PublishArgs {
+    /// Date to generate the church date for (YYYY-MM-DD), defaults to today
+    #[arg(long)]
+    pub date: Option<NaiveDate>,
+
+    /// Sermon title, without quotes. An empty title means no sermon title
+    #[arg(long)]
+    pub sermon_title: Option<String>,
+
+    /// Extra line for the OBS lower third, can be given more than once
+    #[arg(long)]
+    pub extra_text: Vec<String>,
+
+    /// Liturgical color for OBS, instead of the suggested one
+    #[arg(long, ignore_case = true, value_parser = PossibleValuesParser::new(COLOR_NAMES))]
+    pub color: Option<String>,
+
+    /// Title for the YouTube live stream, instead of the generated one
+    #[arg(long)]
+    pub youtube_title: Option<String>,
+
+    /// Accept every suggestion and skip questions that need typing
+    #[arg(short, long)]
+    pub yes: bool,
+
+    /// Don't update the YouTube title
+    #[arg(long)]
+    pub no_youtube: bool,
+
+    /// Show what would be changed without changing anything
+    #[arg(long)]
+    pub dry_run: bool,
+}
+
+#[derive(Subcommand)]
+pub enum Command {
+    /// Print today's church date
+    Today,
+    /// Print the next church dates after today
+    Next {
+        /// How many church dates to print
+        #[arg(short = 'n', long, default_value_t = 1)]
+        count: usize,
+    },
+    /// Print the liturgical calendar: `calendar`, `calendar -y`, `calendar <year>` or
+    /// `calendar <month> <year>`, or list the church dates between two dates
+    #[command(visible_alias = "cal")]
+    Calendar {
+        /// First date to list (YYYY-MM-DD)
+        #[arg(long, requires = "to")]
+        from: Option<NaiveDate>,
+        /// Last date to list (YYYY-MM-DD)
+        #[arg(long, requires = "from")]
+        to: Option<NaiveDate>,
+        #[arg(allow_hyphen_values = true, conflicts_with = "from")]
+        args: Vec<String>,
+    },
+    /// Write church dates to a file as ics (calendar), csv or json
+    Export {
+        /// ics, csv or json
+        #[arg(short, long, default_value = "ics")]
+        format: ExportFormat,
+        /// First date to export (YYYY-MM-DD), defaults to the start of this year
+        #[arg(long)]
+        from: Option<NaiveDate>,
+        /// Last date to export (YYYY-MM-DD), defaults to the end of this year
+        #[arg(long)]
+        to: Option<NaiveDate>,
+        /// File to write to, defaults to printing it
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Update the OBS lower third, color and YouTube title (the same as no command)
+    Publish(PublishArgs),
+    /// Prepare the lower third, color and YouTube title on one screen, then publish them
+    Tui {
+        /// Date to prepare for (YYYY-MM-DD), defaults to today
+        #[arg(long)]
+        date: Option<NaiveDate>,
+        /// Show what would be changed without changing anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Show what was published, the newest last
+    History {
+        /// How many runs to show
+        #[arg(short = 'n', long, default_value_t = 10)]
+        count: usize,
+    },
+    /// Put back the lower third, color and YouTube title from before the last run
+    Undo {
+        /// Don't ask before undoing
+        #[arg(short, long)]
+        yes: bool,
+    },
+    /// Put the images, overlay and lower third file where the program looks for them
+    Init {
+        /// Folder to set up, instead of the data folder from the settings
+        directory: Option<PathBuf>,
+    },
+    /// Host the overlay on a local web server, updated the moment something is published
+    Serve {
+        /// Port on localhost, instead of the one from the settings
+        #[arg(short, long)]
+        port: Option<u16>,
+    },
+}
@@ -0,0 +1,1050 @@
+use chrono::{Datelike, NaiveDate, NaiveTime};
+use serde::{Deserialize, Deserializer, Serialize};
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use church_date_updater::lib_church_date::{
+    abbreviated_church_date, date_ordinal, lectionary_year, Color, OrdinalLength, Season, Tradition,
+};
+use church_date_updater::lib_sermon_title::quoted_sermon_title;
+use church_date_updater::lib_template::{Template, TemplateValue};
+
+pub const CONFIG_FILE_NAME: &str = "church-date-updater.toml";
+
+// the folder in the user's config and data folders
+const APP_DIRECTORY_NAME: &str = "church-date-updater";
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
+pub enum Locale {
+    // 10-25-26, October 25th
+    #[default]
+    EnUs,
+    // 25-10-26, 25th October
+    EnGb,
+}
+
+impl Locale {
+    pub fn numeric_date_format(self) -> &'static str {
+        match self {
+            Locale::EnUs => "%m-%d-%y",
+            Locale::EnGb => "%d-%m-%y",
+        }
+    }
+
+    pub fn long_date(self, month: &str, ordinal_day: &str) -> String {
+        match self {
+            Locale::EnUs => format!("{month} {ordinal_day}"),
+            Locale::EnGb => format!("{ordinal_day} {month}"),
+        }
+    }
+}
+
+impl FromStr for Locale {
+    type Err = String;
+
+    fn from_str(locale: &str) -> Result<Self, Self::Err> {
+        match locale.to_lowercase().replace('_', "-").as_str() {
+            "en-us" => Ok(Locale::EnUs),
+            "en-gb" => Ok(Locale::EnGb),
+            _ => Err(format!("'{locale}' isn't a locale, use en-US or en-GB")),
+        }
+    }
+}
+
+// for types from the library that don't use serde
+fn from_string<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    let text = String::deserialize(deserializer)?;
+    text.parse().map_err(serde::de::Error::custom)
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    #[serde(deserialize_with = "from_string")]
+    pub tradition: Tradition,
+    #[serde(deserialize_with = "from_string")]
+    pub locale: Locale,
+    pub paths: Paths,
+    pub titles: Titles,
+    pub sermon_titles: SermonTitles,
+    pub lower_third: LowerThirdSettings,
+    pub overlay: OverlaySettings,
+    pub server: ServerSettings,
+    pub obs: ObsSettings,
+    pub steps: Steps,
+    // `[profiles.chapel]` has the settings that are different for `--profile
+    // chapel`, like its own tradition, data directory and YouTube channel
+    pub profiles: toml::Table,
+}
+
+// relative paths are relative to the data directory, see `data_directory`
+#[derive(Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Paths {
+    // relative to the folder the settings file is in
+    pub data_directory: Option<PathBuf>,
+    pub lower_third: PathBuf,
+    // the same lines with how long and when they're shown, for the overlay
+    pub lower_third_json: PathBuf,
+    // `White.png`, `Green.png`, etc.
+    pub image_directory: PathBuf,
+    // the image OBS shows, replaced by a copy of the color's image
+    pub color_image: PathBuf,
+    pub youtube_secret: PathBuf,
+    pub youtube_token_cache: PathBuf,
+    // sermon titles and extra text planned ahead, `.toml` or `.csv`
+    pub plan: PathBuf,
+    // every run is added to the end, for `history` and `undo`
+    pub history: PathBuf,
+    // lines added to the lower third every week, changed from the setup
+    pub recurring_lines: PathBuf,
+    // an overlay template of your own instead of the built-in one
+    pub overlay_template: Option<PathBuf>,
+}
+
+impl Default for Paths {
+    fn default() -> Self {
+        Paths {
+            data_directory: None,
+            lower_third: PathBuf::from("lower_data.txt"),
+            lower_third_json: PathBuf::from("lower_data.json"),
+            image_directory: PathBuf::from("pics"),
+            color_image: PathBuf::from("pics/Current_Color.png"),
+            youtube_secret: PathBuf::from("secret.json"),
+            youtube_token_cache: PathBuf::from("tokencache.json"),
+            plan: PathBuf::from("plan.toml"),
+            history: PathBuf::from("history.jsonl"),
+            recurring_lines: PathBuf::from("recurring_lines.txt"),
+            overlay_template: None,
+        }
+    }
+}
+
+impl Paths {
+    // absolute paths stay the same
+    pub fn resolved(self, data_directory: &Path) -> Paths {
+        Paths {
+            data_directory: Some(data_directory.to_path_buf()),
+            lower_third: data_directory.join(self.lower_third),
+            lower_third_json: data_directory.join(self.lower_third_json),
+            image_directory: data_directory.join(self.image_directory),
+            color_image: data_directory.join(self.color_image),
+            youtube_secret: data_directory.join(self.youtube_secret),
+            youtube_token_cache: data_directory.join(self.youtube_token_cache),
+            plan: data_directory.join(self.plan),
+            history: data_directory.join(self.history),
+            recurring_lines: data_directory.join(self.recurring_lines),
+            overlay_template: self
+                .overlay_template
+                .map(|template| data_directory.join(template)),
+        }
+    }
+}
+
+// see README.MD for the placeholders
+#[derive(Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Titles {
+    #[serde(deserialize_with = "from_string")]
+    pub lower_third_church_date: Template,
+    // only used when there is a sermon title
+    #[serde(deserialize_with = "from_string")]
+    pub lower_third_sermon_title: Template,
+    #[serde(deserialize_with = "from_string")]
+    pub youtube: Template,
+}
+
+impl Default for Titles {
+    fn default() -> Self {
+        let template = |text: &str| {
+            text.parse()
+                .expect("The default title templates should be valid.")
+        };
+        Titles {
+            lower_third_church_date: template("{long_date}, {church_date}"),
+            lower_third_sermon_title: template("{quoted_sermon_title}"),
+            // the first extra text replaces the sermon title
+            youtube: template(
+                "{#extra_text.1}{extra_text.1} - {/extra_text.1}\
+                 {^extra_text.1}{#sermon_title}{quoted_sermon_title} - {/sermon_title}{/extra_text.1}\
+                 {date} - {church_date}",
+            ),
+        }
+    }
+}
+
+impl Titles {
+    // unknown placeholders are found when the settings are loaded, not halfway
+    // through the setup
+    fn check(&self) -> Result<(), String> {
+        let example = TitleValues {
+            date: NaiveDate::from_ymd_opt(2026, 10, 25).expect("Should be a valid date."),
+            locale: Locale::default(),
+            church_date: "Reformation Sunday",
+            observance_short: "Reformation Sunday",
+            observance_long: "Reformation Sunday",
+            season: Season::Pentecost,
+            color: Color::Red,
+            sermon_title: "Here I Stand",
+            typographic_quotes: false,
+            preacher: "Pastor Ann",
+            extra_texts: &["Confirmation".to_string()],
+        };
+        for (name, template) in [
+            ("lower_third_church_date", &self.lower_third_church_date),
+            ("lower_third_sermon_title", &self.lower_third_sermon_title),
+            ("youtube", &self.youtube),
+        ] {
+            template
+                .render(|placeholder| example.get(placeholder))
+                .map_err(|err| format!("The title '{name}' can't be used: {err}"))?;
+        }
+        Ok(())
+    }
+}
+
+// YouTube doesn't accept longer titles
+pub const YOUTUBE_TITLE_LIMIT: usize = 100;
+
+// everything a title template can use
+#[derive(Clone, Copy)]
+pub struct TitleValues<'a> {
+    pub date: NaiveDate,
+    pub locale: Locale,
+    // the church date as it was confirmed for OBS
+    pub church_date: &'a str,
+    pub observance_short: &'a str,
+    pub observance_long: &'a str,
+    pub season: Season,
+    pub color: Color,
+    pub sermon_title: &'a str,
+    pub typographic_quotes: bool,
+    pub preacher: &'a str,
+    pub extra_texts: &'a [String],
+}
+
+impl TitleValues<'_> {
+    pub fn get(&self, name: &str) -> Option<TemplateValue> {
+        let text = |text: &str| Some(TemplateValue::Text(text.to_string()));
+        match name {
+            "date" => Some(TemplateValue::Date(
+                self.date,
+                self.locale.numeric_date_format().to_string(),
+            )),
+            "long_date" => text(&self.locale.long_date(
+                &self.date.format("%B").to_string(),
+                &date_ordinal(self.date.day().into(), OrdinalLength::Short),
+            )),
+            "church_date" => text(self.church_date),
+            "observance.short" => text(self.observance_short),
+            "observance.long" => text(self.observance_long),
+            "season" => text(&self.season.to_string()),
+            "lectionary_year" => text(&lectionary_year(self.date).to_string()),
+            "color" => text(&self.color.to_string()),
+            "sermon_title" => text(self.sermon_title),
+            "quoted_sermon_title" if self.sermon_title.is_empty() => text(""),
+            "quoted_sermon_title" => text(&quoted_sermon_title(
+                self.sermon_title,
+                self.typographic_quotes,
+            )),
+            "preacher" => text(self.preacher),
+            "extra_text" => text(&self.extra_texts.join(" - ")),
+            // `extra_text.1` is the first extra text
+            _ => {
+                let number: usize = name.strip_prefix("extra_text.")?.parse().ok()?;
+                let extra_text = match number {
+                    0 => return None,
+                    number => self.extra_texts.get(number - 1).map_or("", String::as_str),
+                };
+                text(extra_text)
+            }
+        }
+    }
+
+    pub fn fill(&self, template: &Template) -> String {
+        template
+            .render(|name| self.get(name))
+            .expect("Title templates should be checked when the settings are loaded.")
+    }
+
+    // tries short ordinals, then "Pentecost 22" instead of "22nd Sunday after
+    // Pentecost", then leaving out the date, until the title fits
+    pub fn shortened(&self, template: &Template, max_length: usize) -> String {
+        // a church date that was changed by hand stays the same
+        let short_church_date = if self.church_date == self.observance_long {
+            self.observance_short
+        } else {
+            self.church_date
+        };
+        let short_ordinals = TitleValues {
+            church_date: short_church_date,
+            observance_long: self.observance_short,
+            ..*self
+        };
+
+        let abbreviated_observance = abbreviated_church_date(self.observance_short);
+        let abbreviated_church_date = abbreviated_church_date(short_church_date);
+        let abbreviated = TitleValues {
+            church_date: &abbreviated_church_date,
+            observance_short: &abbreviated_observance,
+            observance_long: &abbreviated_observance,
+            ..*self
+        };
+
+        let mut title = String::new();
+        for (values, without_date) in [
+            (short_ordinals, false),
+            (abbreviated, false),
+            (abbreviated, true),
+        ] {
+            title = template
+                .render(|name| match name {
+                    "date" | "long_date" if without_date => {
+                        Some(TemplateValue::Text(String::new()))
+                    }
+                    name => values.get(name),
+                })
+                .expect("Title templates should be checked when the settings are loaded.");
+            if without_date {
+                // the separator that was next to the date
+                title = title
+                    .split(" - ")
+                    .filter(|part| !part.trim().is_empty())
+                    .collect::<Vec<_>>()
+                    .join(" - ");
+            }
+            if title.chars().count() <= max_length {
+                break;
+            }
+        }
+        title
+    }
+}
+
+// what sermon titles are checked for
+#[derive(Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SermonTitles {
+    // names that don't belong in a sermon title, the preacher from the plan is
+    // checked too
+    pub preachers: Vec<String>,
+    pub max_length: usize,
+    // “All Are Welcome” instead of "All Are Welcome"
+    pub typographic_quotes: bool,
+}
+
+impl Default for SermonTitles {
+    fn default() -> Self {
+        SermonTitles {
+            preachers: Vec::new(),
+            max_length: 50,
+            typographic_quotes: false,
+        }
+    }
+}
+
+// a line and how the overlay shows it, anything not set is up to the overlay
+#[derive(Deserialize, Serialize, Clone, Default, PartialEq, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct OverlayLine {
+    pub text: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub duration_seconds: Option<u64>,
+    // only shown from `start` until `end` on the day, like "10:45"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub start: Option<NaiveTime>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub end: Option<NaiveTime>,
+    // a class for the overlay, like "small" or "italic"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub style: Option<String>,
+}
+
+// a standard line is only text, or text with how it's shown
+#[derive(Deserialize, Clone, PartialEq, Debug)]
+#[serde(untagged)]
+pub enum StandardLine {
+    Text(String),
+    WithOptions(OverlayLine),
+}
+
+impl StandardLine {
+    pub fn text(&self) -> &str {
+        match self {
+            StandardLine::Text(text) => text,
+            StandardLine::WithOptions(line) => &line.text,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LowerThirdSettings {
+    // how long each line is shown, unless the line says otherwise
+    pub duration_seconds: u64,
+    // lines that can be picked when changing the lower third, like
+    // "Welcome! Giving: example.org/give"
+    pub standard_lines: Vec<StandardLine>,
+}
+
+impl Default for LowerThirdSettings {
+    fn default() -> Self {
+        LowerThirdSettings {
+            duration_seconds: 60,
+            standard_lines: Vec::new(),
+        }
+    }
+}
+
+impl LowerThirdSettings {
+    // a line is shown the way its standard line says, found by its text
+    pub fn overlay_line(&self, text: &str) -> OverlayLine {
+        let line = self
+            .standard_lines
+            .iter()
+            .find_map(|standard_line| match standard_line {
+                StandardLine::WithOptions(line) if line.text == text => Some(line.clone()),
+                StandardLine::Text(_) | StandardLine::WithOptions(_) => None,
+            })
+            .unwrap_or_else(|| OverlayLine {
+                text: text.to_string(),
+                ..OverlayLine::default()
+            });
+        OverlayLine {
+            duration_seconds: line.duration_seconds.or(Some(self.duration_seconds)),
+            ..line
+        }
+    }
+}
+
+// where the overlay shows the lower third
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
+pub enum OverlayLayout {
+    // in the top right, flying in from the side
+    #[default]
+    FlyIn,
+    // across the bottom
+    Banner,
+    // the whole screen with every line at once, for before the service
+    Slide,
+}
+
+impl OverlayLayout {
+    pub fn name(self) -> &'static str {
+        match self {
+            OverlayLayout::FlyIn => "fly-in",
+            OverlayLayout::Banner => "banner",
+            OverlayLayout::Slide => "slide",
+        }
+    }
+}
+
+impl FromStr for OverlayLayout {
+    type Err = String;
+
+    fn from_str(layout: &str) -> Result<Self, Self::Err> {
+        match layout.to_lowercase().replace('_', "-").as_str() {
+            "fly-in" => Ok(OverlayLayout::FlyIn),
+            "banner" => Ok(OverlayLayout::Banner),
+            "slide" => Ok(OverlayLayout::Slide),
+            _ => Err(format!(
+                "'{layout}' isn't an overlay layout, use fly-in, banner or slide"
+            )),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct OverlaySettings {
+    #[serde(deserialize_with = "from_string")]
+    pub layout: OverlayLayout,
+    // CSS, like "Arial" or "'Open Sans', sans-serif"
+    pub font: String,
+}
+
+impl Default for OverlaySettings {
+    fn default() -> Self {
+        OverlaySettings {
+            layout: OverlayLayout::default(),
+            font: "Arial".to_string(),
+        }
+    }
+}
+
+// `serve`, only on this computer
+#[derive(Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ServerSettings {
+    pub port: u16,
+}
+
+impl Default for ServerSettings {
+    fn default() -> Self {
+        ServerSettings { port: 8750 }
+    }
+}
+
+// OBS's WebSocket server, in OBS under Tools, WebSocket Server Settings
+#[derive(Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ObsSettings {
+    pub host: String,
+    pub port: u16,
+    // empty when authentication is turned off
+    pub password: String,
+    // the sources that are changed, the ones that aren't set are left alone
+    // a text source gets the lower third, one line per line
+    pub text_source: Option<String>,
+    // an image source gets the color's image
+    pub image_source: Option<String>,
+    // a browser source with the overlay is refreshed
+    pub browser_source: Option<String>,
+}
+
+impl Default for ObsSettings {
+    fn default() -> Self {
+        ObsSettings {
+            host: "localhost".to_string(),
+            port: 4455,
+            password: String::new(),
+            text_source: None,
+            image_source: None,
+            browser_source: None,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Steps {
+    pub lower_third: bool,
+    pub color: bool,
+    // the overlay is made again in the color of the day
+    pub overlay: bool,
+    // the sources in `[obs]` are set directly, off unless OBS's WebSocket
+    // server is used
+    pub obs: bool,
+    pub youtube: bool,
+    // how long to wait at the end so the messages can be read
+    pub wait_seconds: u64,
+    // the outputs are written atomically now, so there's nothing to wait for,
+    // older settings with it still load
+    obs_delay_ms: Option<u64>,
+}
+
+impl Default for Steps {
+    fn default() -> Self {
+        Steps {
+            lower_third: true,
+            color: true,
+            overlay: true,
+            obs: false,
+            youtube: true,
+            wait_seconds: 3,
+            obs_delay_ms: None,
+        }
+    }
+}
+
+pub fn exe_directory() -> Option<PathBuf> {
+    std::env::current_exe()
+        .ok()
+        .and_then(|exe| exe.parent().map(PathBuf::from))
+}
+
+pub fn user_config_directory() -> Option<PathBuf> {
+    dirs::config_dir().map(|config_directory| config_directory.join(APP_DIRECTORY_NAME))
+}
+
+// next to the program first, so every machine can share one folder, then the
+// user's config folder
+fn config_locations() -> Vec<PathBuf> {
+    [exe_directory(), user_config_directory()]
+        .into_iter()
+        .flatten()
+        .map(|directory| directory.join(CONFIG_FILE_NAME))
+        .collect()
+}
+
+// where the lower third, images, history and YouTube files are, never the
+// folder the program happens to be started in (a shortcut can start it anywhere)
+pub fn data_directory(paths: &Paths, config_location: Option<&Path>) -> PathBuf {
+    find_data_directory(
+        paths,
+        config_location,
+        exe_directory().as_deref(),
+        dirs::data_dir()
+            .map(|data_directory| data_directory.join(APP_DIRECTORY_NAME))
+            .as_deref(),
+    )
+}
+
+// the folder from the settings, then next to the program when it's set up
+// there (see README.MD), then the user's data folder
+fn find_data_directory(
+    paths: &Paths,
+    config_location: Option<&Path>,
+    exe_directory: Option<&Path>,
+    user_data_directory: Option<&Path>,
+) -> PathBuf {
+    if let Some(data_directory) = &paths.data_directory {
+        let config_directory = config_location.and_then(Path::parent);
+        return match config_directory {
+            Some(config_directory) => config_directory.join(data_directory),
+            None => data_directory.clone(),
+        };
+    }
+    if let Some(exe_directory) = exe_directory {
+        let is_set_up = exe_directory.join(CONFIG_FILE_NAME).is_file()
+            || exe_directory.join(&paths.image_directory).is_dir()
+            || exe_directory.join(&paths.lower_third).is_file();
+        if is_set_up || user_data_directory.is_none() {
+            return exe_directory.to_path_buf();
+        }
+    }
+    user_data_directory
+        .map(Path::to_path_buf)
+        .unwrap_or_else(|| PathBuf::from("."))
+}
+
+// a profile's settings replace the ones outside of it, tables like `[paths]`
+// only replace the settings they have
+fn merge(settings: &mut toml::Table, profile: &toml::Table) {
+    for (name, value) in profile {
+        match (settings.get_mut(name), value) {
+            (Some(toml::Value::Table(table)), toml::Value::Table(profile_table)) => {
+                merge(table, profile_table);
+            }
+            _ => {
+                settings.insert(name.clone(), value.clone());
+            }
+        }
+    }
+}
+
+fn parse_config(text: &str, profile: Option<&str>) -> Result<Config, String> {
+    // read on its own first, for the line numbers in mistakes
+    let config: Config = toml::from_str(text).map_err(|err| err.to_string())?;
+    let config = match profile {
+        None => config,
+        Some(profile) => {
+            let Some(toml::Value::Table(profile_settings)) = config.profiles.get(profile) else {
+                let profiles = config.profiles.keys().cloned().collect::<Vec<_>>();
+                return Err(if profiles.is_empty() {
+                    format!("There isn't a profile named '{profile}', there aren't any profiles.")
+                } else {
+                    format!(
+                        "There isn't a profile named '{profile}', use one of {}.",
+                        profiles.join(", ")
+                    )
+                });
+            };
+            let mut settings: toml::Table = toml::from_str(text).map_err(|err| err.to_string())?;
+            settings.remove("profiles");
+            merge(&mut settings, profile_settings);
+            settings
+                .try_into()
+                .map_err(|err| format!("In the '{profile}' profile: {err}"))?
+        }
+    };
+    config.titles.check()?;
+    Ok(config)
+}
+
+// the defaults are used when there isn't a config file
+pub fn load_config(profile: Option<&str>) -> Result<(Config, Option<PathBuf>), String> {
+    for location in config_locations() {
+        if !location.is_file() {
+            continue;
+        }
+        let text = fs::read_to_string(&location)
+            .map_err(|err| format!("Unable to read '{}': {err}", location.display()))?;
+        let config = parse_config(&text, profile)
+            .map_err(|err| format!("Unable to use '{}': {err}", location.display()))?;
+        if config.steps.obs_delay_ms.is_some() {
+            println!(
+                "'obs_delay_ms' in '{}' isn't used anymore and can be removed.",
+                location.display()
+            );
+        }
+        return Ok((config, Some(location)));
+    }
+    match profile {
+        Some(profile) => Err(format!(
+            "There isn't a profile named '{profile}', there isn't a {CONFIG_FILE_NAME} yet."
+        )),
+        None => Ok((Config::default(), None)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lib_test_directory::test_directory;
+
+    #[test]
+    fn empty_config_uses_defaults() {
+        let config: Config = toml::from_str("").unwrap();
+        assert_eq!(config.tradition, Tradition::Lutheran);
+        assert_eq!(config.locale, Locale::EnUs);
+        assert_eq!(config.paths.lower_third, PathBuf::from("lower_data.txt"));
+        assert_eq!(
+            config.titles.lower_third_church_date.to_string(),
+            "{long_date}, {church_date}"
+        );
+        assert!(config.steps.youtube);
+        assert_eq!(config.steps.wait_seconds, 3);
+    }
+
+    #[test]
+    fn partial_config() {
+        let config: Config = toml::from_str(
+            r#"
+            tradition = "ecumenical"
+            locale = "en-GB"
+
+            [paths]
+            image_directory = "colors"
+
+            [lower_third]
+            standard_lines = [
+                "Welcome!",
+                { text = "Giving: example.org/give", start = "10:45", style = "small" },
+            ]
+
+            [overlay]
+            layout = "banner"
+
+            [obs]
+            password = "hunter2"
+            text_source = "Lower third"
+
+            [steps]
+            youtube = false
+            "#,
+        )
+        .unwrap();
+        assert_eq!(config.tradition, Tradition::Ecumenical);
+        assert_eq!(config.locale, Locale::EnGb);
+        assert_eq!(config.paths.image_directory, PathBuf::from("colors"));
+        assert_eq!(
+            config.paths.color_image,
+            PathBuf::from("pics/Current_Color.png")
+        );
+        let standard_lines = &config.lower_third.standard_lines;
+        assert_eq!(
+            standard_lines[0],
+            StandardLine::Text("Welcome!".to_string())
+        );
+        assert_eq!(standard_lines[1].text(), "Giving: example.org/give");
+        assert_eq!(
+            config.lower_third.overlay_line("Giving: example.org/give"),
+            OverlayLine {
+                text: "Giving: example.org/give".to_string(),
+                duration_seconds: Some(60),
+                start: NaiveTime::from_hms_opt(10, 45, 0),
+                end: None,
+                style: Some("small".to_string()),
+            }
+        );
+        assert_eq!(
+            config.lower_third.overlay_line("Welcome!"),
+            OverlayLine {
+                text: "Welcome!".to_string(),
+                duration_seconds: Some(60),
+                ..OverlayLine::default()
+            }
+        );
+        assert_eq!(config.overlay.layout, OverlayLayout::Banner);
+        assert_eq!(config.overlay.font, "Arial");
+        assert_eq!(config.obs.port, 4455);
+        assert_eq!(config.obs.text_source.as_deref(), Some("Lower third"));
+        assert_eq!(config.obs.image_source, None);
+        assert!(!config.steps.obs);
+        assert!(!config.steps.youtube);
+        assert!(config.steps.color);
+    }
+
+    #[test]
+    fn unknown_settings_are_errors() {
+        assert!(toml::from_str::<Config>("[steps]\nyoutub = false").is_err());
+        assert!(toml::from_str::<Config>("locale = \"fr-FR\"").is_err());
+        assert!(toml::from_str::<Config>("[overlay]\nlayout = \"sidebar\"").is_err());
+        assert!(toml::from_str::<Config>(
+            "[lower_third]\nstandard_lines = [{ text = \"Hi\", stlye = \"small\" }]"
+        )
+        .is_err());
+        assert!(toml::from_str::<Config>(
+            "[lower_third]\nstandard_lines = [{ text = \"Hi\", start = \"noon\" }]"
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn settings_that_were_removed() {
+        let config = parse_config("[steps]\nobs_delay_ms = 200\nyoutube = false", None).unwrap();
+        assert_eq!(config.steps.obs_delay_ms, Some(200));
+        assert!(!config.steps.youtube);
+        assert_eq!(parse_config("", None).unwrap().steps.obs_delay_ms, None);
+    }
+
+    #[test]
+    fn bad_templates_are_errors() {
+        assert!(toml::from_str::<Config>("[titles]\nyoutube = \"{date\"").is_err());
+        assert!(toml::from_str::<Config>("[titles]\nyoutube = \"{date:%m-%d-%y}\"").is_ok());
+    }
+
+    #[test]
+    fn unknown_placeholders_are_errors() {
+        let config: Config = toml::from_str("[titles]\nyoutube = \"{speaker}\"").unwrap();
+        assert!(config.titles.check().is_err());
+        assert!(Config::default().titles.check().is_ok());
+    }
+
+    #[test]
+    fn default_youtube_titles() {
+        let extra_texts = ["Confirmation".to_string()];
+        let mut values = TitleValues {
+            date: NaiveDate::from_ymd_opt(2026, 10, 25).unwrap(),
+            locale: Locale::EnUs,
+            church_date: "Reformation Sunday",
+            observance_short: "Reformation Sunday",
+            observance_long: "Reformation Sunday",
+            season: Season::Pentecost,
+            color: Color::Red,
+            sermon_title: "",
+            typographic_quotes: false,
+            preacher: "",
+            extra_texts: &[],
+        };
+        let titles = Titles::default();
+        assert_eq!(
+            values.fill(&titles.youtube),
+            "10-25-26 - Reformation Sunday"
+        );
+        values.sermon_title = "All Are Welcome";
+        assert_eq!(
+            values.fill(&titles.youtube),
+            "\"All Are Welcome\" - 10-25-26 - Reformation Sunday"
+        );
+        values.typographic_quotes = true;
+        assert_eq!(
+            values.fill(&titles.lower_third_sermon_title),
+            "“All Are Welcome”"
+        );
+        values.extra_texts = &extra_texts;
+        assert_eq!(
+            values.fill(&titles.youtube),
+            "Confirmation - 10-25-26 - Reformation Sunday"
+        );
+        assert_eq!(
+            values.fill(&titles.lower_third_church_date),
+            "October 25th, Reformation Sunday"
+        );
+    }
+
+    #[test]
+    fn shortened_youtube_titles() {
+        let values = TitleValues {
+            date: NaiveDate::from_ymd_opt(2026, 9, 27).unwrap(),
+            locale: Locale::EnUs,
+            church_date: "Nineteenth Sunday after Pentecost",
+            observance_short: "19th Sunday after Pentecost",
+            observance_long: "Nineteenth Sunday after Pentecost",
+            season: Season::Pentecost,
+            color: Color::Green,
+            sermon_title: "The Very Long Sermon Title That Goes On",
+            typographic_quotes: false,
+            preacher: "",
+            extra_texts: &[],
+        };
+        let youtube = Titles::default().youtube;
+        let title = values.fill(&youtube);
+        assert_eq!(title.len(), 88);
+
+        assert_eq!(
+            values.shortened(&youtube, 85),
+            "\"The Very Long Sermon Title That Goes On\" - 09-27-26 - 19th Sunday after Pentecost"
+        );
+        assert_eq!(
+            values.shortened(&youtube, 70),
+            "\"The Very Long Sermon Title That Goes On\" - 09-27-26 - Pentecost 19"
+        );
+        assert_eq!(
+            values.shortened(&youtube, 60),
+            "\"The Very Long Sermon Title That Goes On\" - Pentecost 19"
+        );
+        // the shortest one when nothing fits
+        assert_eq!(
+            values.shortened(&youtube, 10),
+            "\"The Very Long Sermon Title That Goes On\" - Pentecost 19"
+        );
+
+        let changed_by_hand = TitleValues {
+            church_date: "Blessing of the Backpacks",
+            ..values
+        };
+        assert_eq!(
+            changed_by_hand.shortened(&youtube, 60),
+            "\"The Very Long Sermon Title That Goes On\" - Blessing of the Backpacks"
+        );
+
+        let formatted_date = "{date:%B %-d} - {church_date}".parse().unwrap();
+        assert_eq!(values.shortened(&formatted_date, 12), "Pentecost 19");
+    }
+
+    #[test]
+    fn every_placeholder() {
+        let extra_texts = ["Confirmation".to_string(), "Potluck".to_string()];
+        let values = TitleValues {
+            date: NaiveDate::from_ymd_opt(2026, 10, 25).unwrap(),
+            locale: Locale::EnGb,
+            church_date: "Reformation",
+            observance_short: "22nd Sunday after Pentecost",
+            observance_long: "Twenty-Second Sunday after Pentecost",
+            season: Season::Pentecost,
+            color: Color::Green,
+            sermon_title: "",
+            typographic_quotes: true,
+            preacher: "Pastor Ann",
+            extra_texts: &extra_texts,
+        };
+        let template: Template = "{date:%Y} {long_date} {season} {lectionary_year} {color} \
+            {observance.short} {preacher}[{quoted_sermon_title}] {extra_text} {extra_text.2}[{extra_text.3}]"
+            .parse()
+            .unwrap();
+        assert_eq!(
+            values.fill(&template),
+            "2026 25th October Pentecost A Green 22nd Sunday after Pentecost Pastor Ann[] \
+             Confirmation - Potluck Potluck[]"
+        );
+        assert!(values.get("extra_text.0").is_none());
+    }
+
+    #[test]
+    fn long_dates() {
+        assert_eq!(Locale::EnUs.long_date("October", "25th"), "October 25th");
+        assert_eq!(Locale::EnGb.long_date("October", "25th"), "25th October");
+    }
+
+    #[test]
+    fn data_directories() {
+        let directory = test_directory("data");
+        let exe_directory = directory.join("bin");
+        let user_data_directory = directory.join("data");
+        fs::create_dir_all(&exe_directory).unwrap();
+        let find = |paths: &Paths, config_location: Option<&Path>| {
+            find_data_directory(
+                paths,
+                config_location,
+                Some(&exe_directory),
+                Some(&user_data_directory),
+            )
+        };
+
+        let paths = Paths::default();
+        assert_eq!(find(&paths, None), user_data_directory);
+
+        // set up next to the program like README.MD says
+        fs::create_dir(exe_directory.join("pics")).unwrap();
+        assert_eq!(find(&paths, None), exe_directory);
+
+        let paths = Paths {
+            data_directory: Some(PathBuf::from("stream")),
+            ..Paths::default()
+        };
+        let config_location = directory.join("config").join(CONFIG_FILE_NAME);
+        assert_eq!(
+            find(&paths, Some(&config_location)),
+            directory.join("config").join("stream")
+        );
+
+        let resolved = Paths::default().resolved(&user_data_directory);
+        assert_eq!(
+            resolved.lower_third,
+            user_data_directory.join("lower_data.txt")
+        );
+        assert_eq!(
+            resolved.color_image,
+            user_data_directory.join("pics").join("Current_Color.png")
+        );
+        let absolute = directory.join("secret.json");
+        let resolved = Paths {
+            youtube_secret: absolute.clone(),
+            ..Paths::default()
+        }
+        .resolved(&user_data_directory);
+        assert_eq!(resolved.youtube_secret, absolute);
+
+        fs::remove_dir_all(&directory).unwrap();
+    }
+
+    const PROFILES: &str = r#"
+        tradition = "lutheran"
+
+        [paths]
+        image_directory = "colors"
+        youtube_secret = "secret.json"
+
+        [titles]
+        youtube = "{date} - {church_date}"
+
+        [profiles.chapel]
+        tradition = "ecumenical"
+
+        [profiles.chapel.paths]
+        data_directory = "chapel"
+        youtube_token_cache = "chapel-tokencache.json"
+
+        [profiles.east]
+        titles.youtube = "East Campus - {church_date}"
+    "#;
+
+    #[test]
+    fn profiles() {
+        let config = parse_config(PROFILES, None).unwrap();
+        assert_eq!(config.tradition, Tradition::Lutheran);
+        assert_eq!(config.paths.data_directory, None);
+
+        let chapel = parse_config(PROFILES, Some("chapel")).unwrap();
+        assert_eq!(chapel.tradition, Tradition::Ecumenical);
+        assert_eq!(chapel.paths.data_directory, Some(PathBuf::from("chapel")));
+        assert_eq!(
+            chapel.paths.youtube_token_cache,
+            PathBuf::from("chapel-tokencache.json")
+        );
+        // the rest of `[paths]` stays the same
+        assert_eq!(chapel.paths.image_directory, PathBuf::from("colors"));
+        assert_eq!(chapel.titles.youtube.to_string(), "{date} - {church_date}");
+
+        let east = parse_config(PROFILES, Some("east")).unwrap();
+        assert_eq!(east.tradition, Tradition::Lutheran);
+        assert_eq!(
+            east.titles.youtube.to_string(),
+            "East Campus - {church_date}"
+        );
+    }
+
+    #[test]
+    fn bad_profiles() {
+        assert_eq!(
+            parse_config(PROFILES, Some("west")).err(),
+            Some("There isn't a profile named 'west', use one of chapel, east.".to_string())
+        );
+        assert!(parse_config("", Some("chapel")).is_err());
+        // mistakes in a profile are found when it's used
+        let text = "[profiles.chapel]\ntradition = \"anglican\"";
+        assert!(parse_config(text, None).is_ok());
+        assert!(parse_config(text, Some("chapel")).is_err());
+        let text = "[profiles.chapel.titles]\nyoutube = \"{nope}\"";
+        assert!(parse_config(text, Some("chapel")).is_err());
+    }
+}
@@ -0,0 +1,342 @@
+use chrono::NaiveDate;
+use std::fmt;
+
+// The Gregorian calendar was introduced in October 1582, so 1583 is the first
+// full year it can be used for. The last year keeps dates at four digits.
+pub const FIRST_GREGORIAN_YEAR: i32 = 1583;
+// The Council of Nicaea set the rules for the date of Easter in 325.
+pub const FIRST_JULIAN_YEAR: i32 = 326;
+pub const LAST_YEAR: i32 = 9999;
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum EasterError {
+    OutOfRange { year: i32, first: i32, last: i32 },
+}
+
+impl fmt::Display for EasterError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            EasterError::OutOfRange { year, first, last } => write!(
+                f,
+                "Easter can only be calculated for the years {first} to {last}, not {year}."
+            ),
+        }
+    }
+}
+
+impl std::error::Error for EasterError {}
+
+fn check_range(year: i32, first: i32) -> Result<(), EasterError> {
+    if (first..=LAST_YEAR).contains(&year) {
+        Ok(())
+    } else {
+        Err(EasterError::OutOfRange {
+            year,
+            first,
+            last: LAST_YEAR,
+        })
+    }
+}
+
+// Western Easter, using the anonymous Gregorian algorithm (Meeus/Jones/Butcher)
+pub fn gregorian_easter(year: i32) -> Result<NaiveDate, EasterError> {
+    check_range(year, FIRST_GREGORIAN_YEAR)?;
+
+    let a = year % 19;
+    let b = year / 100;
+    let c = year % 100;
+    let d = b / 4;
+    let e = b % 4;
+    let f = (b + 8) / 25;
+    let g = (b - f + 1) / 3;
+    let h = (19 * a + b - d - g + 15) % 30;
+    let i = c / 4;
+    let k = c % 4;
+    let l = (32 + 2 * e + 2 * i - h - k) % 7;
+    let m = (a + 11 * h + 22 * l) / 451;
+    let month = (h + l - 7 * m + 114) / 31;
+    let day = (h + l - 7 * m + 114) % 31 + 1;
+
+    // month and day are always a valid date in March or April
+    #[allow(clippy::cast_sign_loss)]
+    Ok(NaiveDate::from_ymd_opt(year, month as u32, day as u32).unwrap())
+}
+
+// Orthodox Easter, using the Julian algorithm (Meeus). The result is given as a
+// Gregorian date, the way it's written on a calendar today.
+pub fn julian_easter(year: i32) -> Result<NaiveDate, EasterError> {
+    check_range(year, FIRST_JULIAN_YEAR)?;
+
+    let a = year % 4;
+    let b = year % 7;
+    let c = year % 19;
+    let d = (19 * c + 15) % 30;
+    let e = (2 * a + 4 * b - d + 34) % 7;
+    let month = (d + e + 114) / 31;
+    let day = (d + e + 114) % 31 + 1;
+
+    // Julian calendar date -> Julian Day Number -> Gregorian date
+    let shift = (14 - month) / 12;
+    let shifted_year = year + 4800 - shift;
+    let shifted_month = month + 12 * shift - 3;
+    let julian_day_number =
+        day + (153 * shifted_month + 2) / 5 + 365 * shifted_year + shifted_year / 4 - 32083;
+
+    // 1721425 is the Julian Day Number of the day before 0001-01-01
+    Ok(NaiveDate::from_num_days_from_ce_opt(julian_day_number - 1_721_425).unwrap())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // published dates of Western Easter from 1900 to 2100
+    const GREGORIAN_EASTERS: &[&str] = &[
+        "1900-04-15",
+        "1901-04-07",
+        "1902-03-30",
+        "1903-04-12",
+        "1904-04-03",
+        "1905-04-23",
+        "1906-04-15",
+        "1907-03-31",
+        "1908-04-19",
+        "1909-04-11",
+        "1910-03-27",
+        "1911-04-16",
+        "1912-04-07",
+        "1913-03-23",
+        "1914-04-12",
+        "1915-04-04",
+        "1916-04-23",
+        "1917-04-08",
+        "1918-03-31",
+        "1919-04-20",
+        "1920-04-04",
+        "1921-03-27",
+        "1922-04-16",
+        "1923-04-01",
+        "1924-04-20",
+        "1925-04-12",
+        "1926-04-04",
+        "1927-04-17",
+        "1928-04-08",
+        "1929-03-31",
+        "1930-04-20",
+        "1931-04-05",
+        "1932-03-27",
+        "1933-04-16",
+        "1934-04-01",
+        "1935-04-21",
+        "1936-04-12",
+        "1937-03-28",
+        "1938-04-17",
+        "1939-04-09",
+        "1940-03-24",
+        "1941-04-13",
+        "1942-04-05",
+        "1943-04-25",
+        "1944-04-09",
+        "1945-04-01",
+        "1946-04-21",
+        "1947-04-06",
+        "1948-03-28",
+        "1949-04-17",
+        "1950-04-09",
+        "1951-03-25",
+        "1952-04-13",
+        "1953-04-05",
+        "1954-04-18",
+        "1955-04-10",
+        "1956-04-01",
+        "1957-04-21",
+        "1958-04-06",
+        "1959-03-29",
+        "1960-04-17",
+        "1961-04-02",
+        "1962-04-22",
+        "1963-04-14",
+        "1964-03-29",
+        "1965-04-18",
+        "1966-04-10",
+        "1967-03-26",
+        "1968-04-14",
+        "1969-04-06",
+        "1970-03-29",
+        "1971-04-11",
+        "1972-04-02",
+        "1973-04-22",
+        "1974-04-14",
+        "1975-03-30",
+        "1976-04-18",
+        "1977-04-10",
+        "1978-03-26",
+        "1979-04-15",
+        "1980-04-06",
+        "1981-04-19",
+        "1982-04-11",
+        "1983-04-03",
+        "1984-04-22",
+        "1985-04-07",
+        "1986-03-30",
+        "1987-04-19",
+        "1988-04-03",
+        "1989-03-26",
+        "1990-04-15",
+        "1991-03-31",
+        "1992-04-19",
+        "1993-04-11",
+        "1994-04-03",
+        "1995-04-16",
+        "1996-04-07",
+        "1997-03-30",
+        "1998-04-12",
+        "1999-04-04",
+        "2000-04-23",
+        "2001-04-15",
+        "2002-03-31",
+        "2003-04-20",
+        "2004-04-11",
+        "2005-03-27",
+        "2006-04-16",
+        "2007-04-08",
+        "2008-03-23",
+        "2009-04-12",
+        "2010-04-04",
+        "2011-04-24",
+        "2012-04-08",
+        "2013-03-31",
+        "2014-04-20",
+        "2015-04-05",
+        "2016-03-27",
+        "2017-04-16",
+        "2018-04-01",
+        "2019-04-21",
+        "2020-04-12",
+        "2021-04-04",
+        "2022-04-17",
+        "2023-04-09",
+        "2024-03-31",
+        "2025-04-20",
+        "2026-04-05",
+        "2027-03-28",
+        "2028-04-16",
+        "2029-04-01",
+        "2030-04-21",
+        "2031-04-13",
+        "2032-03-28",
+        "2033-04-17",
+        "2034-04-09",
+        "2035-03-25",
+        "2036-04-13",
+        "2037-04-05",
+        "2038-04-25",
+        "2039-04-10",
+        "2040-04-01",
+        "2041-04-21",
+        "2042-04-06",
+        "2043-03-29",
+        "2044-04-17",
+        "2045-04-09",
+        "2046-03-25",
+        "2047-04-14",
+        "2048-04-05",
+        "2049-04-18",
+        "2050-04-10",
+        "2051-04-02",
+        "2052-04-21",
+        "2053-04-06",
+        "2054-03-29",
+        "2055-04-18",
+        "2056-04-02",
+        "2057-04-22",
+        "2058-04-14",
+        "2059-03-30",
+        "2060-04-18",
+        "2061-04-10",
+        "2062-03-26",
+        "2063-04-15",
+        "2064-04-06",
+        "2065-03-29",
+        "2066-04-11",
+        "2067-04-03",
+        "2068-04-22",
+        "2069-04-14",
+        "2070-03-30",
+        "2071-04-19",
+        "2072-04-10",
+        "2073-03-26",
+        "2074-04-15",
+        "2075-04-07",
+        "2076-04-19",
+        "2077-04-11",
+        "2078-04-03",
+        "2079-04-23",
+        "2080-04-07",
+        "2081-03-30",
+        "2082-04-19",
+        "2083-04-04",
+        "2084-03-26",
+        "2085-04-15",
+        "2086-03-31",
+        "2087-04-20",
+        "2088-04-11",
+        "2089-04-03",
+        "2090-04-16",
+        "2091-04-08",
+        "2092-03-30",
+        "2093-04-12",
+        "2094-04-04",
+        "2095-04-24",
+        "2096-04-15",
+        "2097-03-31",
+        "2098-04-20",
+        "2099-04-12",
+        "2100-03-28",
+    ];
+
+    fn date(date: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(date, "%Y-%m-%d").unwrap()
+    }
+
+    #[test]
+    fn gregorian_table() {
+        assert_eq!(GREGORIAN_EASTERS.len(), 201);
+        for (year, easter) in (1900..=2100).zip(GREGORIAN_EASTERS) {
+            assert_eq!(gregorian_easter(year), Ok(date(easter)), "{year}");
+        }
+    }
+
+    #[test]
+    fn gregorian_extremes() {
+        assert_eq!(gregorian_easter(1583), Ok(date("1583-04-10")));
+        assert_eq!(gregorian_easter(2285), Ok(date("2285-03-22")));
+        assert_eq!(gregorian_easter(1943), Ok(date("1943-04-25")));
+        assert_eq!(gregorian_easter(9999), Ok(date("9999-03-28")));
+    }
+
+    #[test]
+    fn julian_dates() {
+        assert_eq!(julian_easter(1900), Ok(date("1900-04-22")));
+        assert_eq!(julian_easter(2021), Ok(date("2021-05-02")));
+        assert_eq!(julian_easter(2023), Ok(date("2023-04-16")));
+        assert_eq!(julian_easter(2024), Ok(date("2024-05-05")));
+        assert_eq!(julian_easter(2025), Ok(date("2025-04-20")));
+        assert_eq!(julian_easter(2100), Ok(date("2100-05-02")));
+    }
+
+    #[test]
+    fn out_of_range() {
+        assert_eq!(
+            gregorian_easter(1582),
+            Err(EasterError::OutOfRange {
+                year: 1582,
+                first: FIRST_GREGORIAN_YEAR,
+                last: LAST_YEAR,
+            })
+        );
+        assert!(gregorian_easter(LAST_YEAR + 1).is_err());
+        assert!(julian_easter(FIRST_JULIAN_YEAR - 1).is_err());
+        assert!(julian_easter(FIRST_JULIAN_YEAR).is_ok());
+    }
+}
@@ -0,0 +1,120 @@
+use chrono::NaiveDate;
+use std::fmt;
+use std::io;
+use std::path::PathBuf;
+
+use crate::lib_easter::EasterError;
+
+// Everything that can go wrong, grouped by what can be done about it.
+#[derive(Debug)]
+pub enum Error {
+    // the church calendar can't be calculated for the year
+    Easter(EasterError),
+    // there isn't a church date on or after the date
+    NoChurchDate(NaiveDate),
+    // reading or writing a file
+    Io { path: PathBuf, source: io::Error },
+    // a question couldn't be asked or answered, like when the terminal is closed
+    Prompt(io::Error),
+    // settings or a plan that can't be used
+    Settings(String),
+    // signing in to YouTube
+    Auth(String),
+    // a YouTube request that didn't work
+    Api(String),
+    // OBS couldn't be reached or didn't accept a request
+    Obs(String),
+    // the overlay server couldn't be started, like when the port is in use
+    Server(String),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Easter(err) => write!(f, "{err}"),
+            Error::NoChurchDate(date) => {
+                write!(f, "There isn't a church date on or after {date}.")
+            }
+            Error::Io { path, source } => {
+                write!(f, "Unable to use '{}': {source}", path.display())
+            }
+            Error::Prompt(err) => write!(f, "Unable to ask the question: {err}"),
+            Error::Settings(err) => write!(f, "{err}"),
+            Error::Auth(err) => write!(f, "Unable to sign in to YouTube: {err}"),
+            Error::Api(err) => write!(f, "YouTube didn't accept the request: {err}"),
+            Error::Obs(err) => write!(f, "Unable to update OBS: {err}"),
+            Error::Server(err) => write!(f, "Unable to start the overlay server: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Easter(err) => Some(err),
+            Error::Io { source, .. } => Some(source),
+            Error::Prompt(err) => Some(err),
+            Error::NoChurchDate(_)
+            | Error::Settings(_)
+            | Error::Auth(_)
+            | Error::Api(_)
+            | Error::Obs(_)
+            | Error::Server(_) => None,
+        }
+    }
+}
+
+impl From<EasterError> for Error {
+    fn from(err: EasterError) -> Self {
+        Error::Easter(err)
+    }
+}
+
+impl Error {
+    pub fn io(path: impl Into<PathBuf>, source: io::Error) -> Self {
+        Error::Io {
+            path: path.into(),
+            source,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lib_church_date::{next_church_date, OrdinalLength, Tradition};
+    use std::error::Error as _;
+
+    #[test]
+    fn calendar_errors() {
+        let date = NaiveDate::from_ymd_opt(1582, 6, 1).unwrap();
+        let Err(err) = next_church_date(date, OrdinalLength::Short, Tradition::Lutheran) else {
+            panic!("1582 is before the Gregorian calendar");
+        };
+        assert!(matches!(
+            err,
+            Error::Easter(EasterError::OutOfRange { year: 1582, .. })
+        ));
+        assert_eq!(
+            err.to_string(),
+            "Easter can only be calculated for the years 1583 to 9999, not 1582."
+        );
+        assert!(err.source().is_some());
+    }
+
+    #[test]
+    fn messages() {
+        let err = Error::io(
+            "lower_data.txt",
+            io::Error::new(io::ErrorKind::PermissionDenied, "access denied"),
+        );
+        assert_eq!(
+            err.to_string(),
+            "Unable to use 'lower_data.txt': access denied"
+        );
+        assert_eq!(
+            Error::Auth("no key".to_string()).to_string(),
+            "Unable to sign in to YouTube: no key"
+        );
+    }
+}
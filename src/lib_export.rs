@@ -0,0 +1,215 @@
+use chrono::{Duration, NaiveDateTime};
+use std::fmt;
+use std::str::FromStr;
+
+use crate::lib_church_date::ChurchDateResult;
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum ExportFormat {
+    // iCalendar, for importing into Google Calendar, Outlook, etc.
+    Ics,
+    Csv,
+    Json,
+}
+
+impl FromStr for ExportFormat {
+    type Err = String;
+
+    fn from_str(format: &str) -> Result<Self, Self::Err> {
+        match format.to_lowercase().as_str() {
+            "ics" | "ical" => Ok(ExportFormat::Ics),
+            "csv" => Ok(ExportFormat::Csv),
+            "json" => Ok(ExportFormat::Json),
+            _ => Err(format!("'{format}' isn't a format, use ics, csv or json")),
+        }
+    }
+}
+
+impl fmt::Display for ExportFormat {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            ExportFormat::Ics => "ics",
+            ExportFormat::Csv => "csv",
+            ExportFormat::Json => "json",
+        };
+        write!(f, "{name}")
+    }
+}
+
+// `created` is the time the export was made, calendars need it for every event
+pub fn export(
+    church_dates: &[ChurchDateResult],
+    format: ExportFormat,
+    created: NaiveDateTime,
+) -> String {
+    match format {
+        ExportFormat::Ics => to_ics(church_dates, created),
+        ExportFormat::Csv => to_csv(church_dates),
+        ExportFormat::Json => to_json(church_dates),
+    }
+}
+
+fn ics_escape(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace('\n', "\\n")
+}
+
+fn to_ics(church_dates: &[ChurchDateResult], created: NaiveDateTime) -> String {
+    // iCalendar lines end with CRLF
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        "PRODID:-//Church Date Updater//EN".to_string(),
+        "CALSCALE:GREGORIAN".to_string(),
+    ];
+
+    for church_date in church_dates {
+        lines.push("BEGIN:VEVENT".to_string());
+        lines.push(format!(
+            "UID:{}@church-date-updater",
+            church_date.date.format("%Y%m%d")
+        ));
+        lines.push(format!("DTSTAMP:{}", created.format("%Y%m%dT%H%M%SZ")));
+        // all day events end on the next day
+        lines.push(format!(
+            "DTSTART;VALUE=DATE:{}",
+            church_date.date.format("%Y%m%d")
+        ));
+        lines.push(format!(
+            "DTEND;VALUE=DATE:{}",
+            (church_date.date + Duration::days(1)).format("%Y%m%d")
+        ));
+        lines.push(format!("SUMMARY:{}", ics_escape(&church_date.text)));
+        lines.push(format!(
+            "DESCRIPTION:{}",
+            ics_escape(&format!(
+                "Season: {}\nColor: {}",
+                church_date.season, church_date.color
+            ))
+        ));
+        lines.push("END:VEVENT".to_string());
+    }
+
+    lines.push("END:VCALENDAR".to_string());
+    lines.join("\r\n") + "\r\n"
+}
+
+fn csv_escape(text: &str) -> String {
+    if text.contains([',', '"', '\n']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text.to_string()
+    }
+}
+
+fn to_csv(church_dates: &[ChurchDateResult]) -> String {
+    let mut csv = String::from("date,observance,color,season\n");
+    for church_date in church_dates {
+        csv.push_str(&format!(
+            "{},{},{},{}\n",
+            church_date.date.format("%Y-%m-%d"),
+            csv_escape(&church_date.text),
+            church_date.color,
+            church_date.season
+        ));
+    }
+    csv
+}
+
+fn json_escape(text: &str) -> String {
+    let mut escaped = String::new();
+    for character in text.chars() {
+        match character {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            character if character.is_control() => {
+                escaped.push_str(&format!("\\u{:04x}", character as u32));
+            }
+            character => escaped.push(character),
+        }
+    }
+    escaped
+}
+
+fn to_json(church_dates: &[ChurchDateResult]) -> String {
+    let entries: Vec<String> = church_dates
+        .iter()
+        .map(|church_date| {
+            format!(
+                "  {{\"date\": \"{}\", \"observance\": \"{}\", \"color\": \"{}\", \"season\": \"{}\"}}",
+                church_date.date.format("%Y-%m-%d"),
+                json_escape(&church_date.text),
+                church_date.color,
+                church_date.season
+            )
+        })
+        .collect();
+    format!("[\n{}\n]\n", entries.join(",\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lib_church_date::{church_dates_between, OrdinalLength, Tradition};
+    use chrono::NaiveDate;
+
+    fn advent_2026() -> Vec<ChurchDateResult> {
+        church_dates_between(
+            NaiveDate::from_ymd_opt(2026, 11, 29).unwrap(),
+            NaiveDate::from_ymd_opt(2026, 12, 6).unwrap(),
+            OrdinalLength::Short,
+            Tradition::Lutheran,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn csv() {
+        assert_eq!(
+            export(&advent_2026(), ExportFormat::Csv, NaiveDateTime::MIN),
+            "date,observance,color,season\n\
+             2026-11-29,1st Sunday of Advent,Blue,Advent\n\
+             2026-12-06,2nd Sunday of Advent,Blue,Advent\n"
+        );
+        assert_eq!(csv_escape("Hello, \"World\""), "\"Hello, \"\"World\"\"\"");
+    }
+
+    #[test]
+    fn json() {
+        assert_eq!(
+            export(&advent_2026(), ExportFormat::Json, NaiveDateTime::MIN),
+            "[\n  {\"date\": \"2026-11-29\", \"observance\": \"1st Sunday of Advent\", \"color\": \"Blue\", \"season\": \"Advent\"},\n  \
+             {\"date\": \"2026-12-06\", \"observance\": \"2nd Sunday of Advent\", \"color\": \"Blue\", \"season\": \"Advent\"}\n]\n"
+        );
+        assert_eq!(json_escape("\"a\\b\"\n"), "\\\"a\\\\b\\\"\\n");
+    }
+
+    #[test]
+    fn ics() {
+        let created = NaiveDate::from_ymd_opt(2026, 10, 19)
+            .unwrap()
+            .and_hms_opt(12, 0, 0)
+            .unwrap();
+        let ics = export(&advent_2026(), ExportFormat::Ics, created);
+        assert!(ics.starts_with("BEGIN:VCALENDAR\r\n"));
+        assert!(ics.ends_with("END:VCALENDAR\r\n"));
+        assert_eq!(ics.matches("BEGIN:VEVENT").count(), 2);
+        assert!(ics.contains("DTSTAMP:20261019T120000Z\r\n"));
+        assert!(ics.contains("DTSTART;VALUE=DATE:20261129\r\nDTEND;VALUE=DATE:20261130\r\n"));
+        assert!(ics.contains("SUMMARY:1st Sunday of Advent\r\n"));
+        assert!(ics.contains("DESCRIPTION:Season: Advent\\nColor: Blue\r\n"));
+    }
+
+    #[test]
+    fn format_names() {
+        assert_eq!("ICS".parse(), Ok(ExportFormat::Ics));
+        assert_eq!("csv".parse(), Ok(ExportFormat::Csv));
+        assert_eq!("json".parse(), Ok(ExportFormat::Json));
+        assert!("pdf".parse::<ExportFormat>().is_err());
+    }
+}
@@ -0,0 +1,169 @@
+use chrono::{NaiveDate, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::Path;
+
+use church_date_updater::lib_error::Error;
+use church_date_updater::lib_youtube_title::TitleChange;
+
+// one line of the history file for every run that changed something, an undo
+// is recorded the same way with `undoes` set to the run it undid
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+pub struct HistoryEntry {
+    pub time: NaiveDateTime,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub undoes: Option<NaiveDateTime>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target_date: Option<NaiveDate>,
+    // what was written, `None` when that step didn't change anything
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lower_third: Option<Vec<String>>,
+    // what was in the lower third before, for undo
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub previous_lower_third: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub color: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub youtube: Option<TitleChange>,
+}
+
+impl HistoryEntry {
+    pub fn changed_anything(&self) -> bool {
+        self.lower_third.is_some() || self.color.is_some() || self.youtube.is_some()
+    }
+}
+
+// one JSON object per line, so a run only ever adds to the end of the file
+pub fn append_history(path: &Path, entry: &HistoryEntry) -> Result<(), Error> {
+    let line = serde_json::to_string(entry).map_err(|err| Error::io(path, err.into()))?;
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .and_then(|mut file| writeln!(file, "{line}"))
+        .map_err(|err| Error::io(path, err))
+}
+
+// there isn't any history before the first run
+pub fn read_history(path: &Path) -> Result<Vec<HistoryEntry>, Error> {
+    if !path.is_file() {
+        return Ok(Vec::new());
+    }
+    let text = fs::read_to_string(path).map_err(|err| Error::io(path, err))?;
+    text.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(number, line)| {
+            serde_json::from_str(line).map_err(|err| {
+                let message = format!("line {}: {err}", number + 1);
+                Error::io(path, io::Error::new(io::ErrorKind::InvalidData, message))
+            })
+        })
+        .collect()
+}
+
+// the newest run that hasn't been undone yet, and the color from before it
+// (the color image can't be read back, so it comes from the runs before)
+pub fn undo_target(entries: &[HistoryEntry]) -> Option<(&HistoryEntry, Option<&str>)> {
+    let undone: HashSet<NaiveDateTime> = entries.iter().filter_map(|entry| entry.undoes).collect();
+    let position = entries
+        .iter()
+        .rposition(|entry| entry.undoes.is_none() && !undone.contains(&entry.time))?;
+    let previous_color = entries[..position]
+        .iter()
+        .rev()
+        .find_map(|entry| entry.color.as_deref());
+    Some((&entries[position], previous_color))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lib_test_directory::test_directory;
+
+    fn entry(time: &str, color: &str, undoes: Option<&str>) -> HistoryEntry {
+        let at = |time: &str| {
+            NaiveDate::from_ymd_opt(2026, 10, 25)
+                .unwrap()
+                .and_time(time.parse().unwrap())
+        };
+        HistoryEntry {
+            time: at(time),
+            undoes: undoes.map(at),
+            target_date: None,
+            lower_third: None,
+            previous_lower_third: None,
+            color: Some(color.to_string()),
+            youtube: None,
+        }
+    }
+
+    #[test]
+    fn undo_newest_run() {
+        let entries = vec![
+            entry("08:00:00", "Green", None),
+            entry("09:00:00", "Red", None),
+        ];
+        let (target, previous_color) = undo_target(&entries).unwrap();
+        assert_eq!(target, &entries[1]);
+        assert_eq!(previous_color, Some("Green"));
+    }
+
+    #[test]
+    fn undo_twice() {
+        let entries = vec![
+            entry("08:00:00", "Green", None),
+            entry("09:00:00", "Red", None),
+            entry("10:00:00", "White", None),
+            entry("10:30:00", "Red", Some("10:00:00")),
+        ];
+        let (target, previous_color) = undo_target(&entries).unwrap();
+        assert_eq!(target, &entries[1]);
+        assert_eq!(previous_color, Some("Green"));
+
+        let entries = vec![
+            entry("08:00:00", "Green", None),
+            entry("09:00:00", "Red", Some("08:00:00")),
+        ];
+        assert!(undo_target(&entries).is_none());
+        assert!(undo_target(&[]).is_none());
+    }
+
+    #[test]
+    fn undo_after_undo_and_run() {
+        let entries = vec![
+            entry("08:00:00", "Green", None),
+            entry("09:00:00", "Red", None),
+            entry("09:30:00", "Green", Some("09:00:00")),
+            entry("10:00:00", "White", None),
+        ];
+        let (target, previous_color) = undo_target(&entries).unwrap();
+        assert_eq!(target, &entries[3]);
+        assert_eq!(previous_color, Some("Green"));
+    }
+
+    #[test]
+    fn history_lines() {
+        let mut first = entry("08:00:00", "Green", None);
+        first.lower_third = Some(vec!["October 25th, Reformation Sunday".to_string()]);
+        first.youtube = Some(TitleChange {
+            broadcast_id: "abc".to_string(),
+            old_title: "Live".to_string(),
+            new_title: "10-25-26 - Reformation Sunday".to_string(),
+        });
+        let second = entry("09:00:00", "Red", Some("08:00:00"));
+
+        let directory = test_directory("history");
+        let path = directory.join("history.jsonl");
+        assert_eq!(read_history(&path).unwrap(), Vec::new());
+        append_history(&path, &first).unwrap();
+        append_history(&path, &second).unwrap();
+        assert_eq!(read_history(&path).unwrap(), vec![first, second]);
+
+        fs::write(&path, "{\"time\": \"yesterday\"}\n").unwrap();
+        assert!(matches!(read_history(&path), Err(Error::Io { .. })));
+        fs::remove_dir_all(&directory).unwrap();
+    }
+}
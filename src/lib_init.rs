@@ -0,0 +1,179 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use church_date_updater::lib_error::Error;
+
+use crate::lib_config::{exe_directory, user_config_directory, Paths, CONFIG_FILE_NAME};
+use crate::lib_output::write_output;
+use crate::lib_overlay::overlay_path;
+
+const NONE_IMAGE: &[u8] = include_bytes!("../pics/None.png");
+
+// everything OBS needs, so `init` works without the source code around
+const COLOR_IMAGES: [(&str, &[u8]); 8] = [
+    ("White", include_bytes!("../pics/White.png")),
+    ("Green", include_bytes!("../pics/Green.png")),
+    ("Purple", include_bytes!("../pics/Purple.png")),
+    ("Red", include_bytes!("../pics/Red.png")),
+    ("Yellow", include_bytes!("../pics/Yellow.png")),
+    ("Blue", include_bytes!("../pics/Blue.png")),
+    ("Black", include_bytes!("../pics/Black.png")),
+    ("None", NONE_IMAGE),
+];
+
+// the overlay in the default layout and no color, until the first run makes it
+// again
+const OVERLAY: &str = include_str!("../church_date.html");
+
+// lays out the files where the program will look for them, without replacing
+// any that are already there
+pub fn init(paths: &Paths, config_location: Option<&Path>) -> Result<(), Error> {
+    let Some(data_directory) = &paths.data_directory else {
+        return Ok(());
+    };
+    println!("Setting up '{}'.", data_directory.display());
+
+    let mut files = expected_files(paths);
+
+    // only when there aren't any settings yet, and in the folder that is
+    // looked in first
+    if config_location.is_none() {
+        if exe_directory().as_ref() == Some(data_directory) {
+            files.push((
+                data_directory.join(CONFIG_FILE_NAME),
+                starter_settings(None),
+            ));
+        } else if let Some(user_config_directory) = user_config_directory() {
+            files.push((
+                user_config_directory.join(CONFIG_FILE_NAME),
+                starter_settings(Some(data_directory)),
+            ));
+        }
+    }
+
+    // the first file that couldn't be created, the others are still tried
+    let mut failure = None;
+    let mut created = 0;
+    for (path, contents) in files {
+        match create_file(&path, &contents) {
+            Ok(true) => {
+                println!("Created '{}'.", path.display());
+                created += 1;
+            }
+            Ok(false) => println!("Okay, '{}' is already there.", path.display()),
+            Err(err) => {
+                println!("Unable to create '{}': {err}", path.display());
+                failure.get_or_insert(Error::io(path, err));
+            }
+        }
+    }
+
+    if created > 0 {
+        println!(
+            "Add '{}' and '{}' to OBS.",
+            paths.color_image.display(),
+            overlay_path(paths).display()
+        );
+    }
+    if !paths.youtube_secret.is_file() {
+        println!(
+            "For the YouTube title, put the key from the YouTube Data API at '{}'.",
+            paths.youtube_secret.display()
+        );
+    }
+    failure.map_or(Ok(()), Err)
+}
+
+fn expected_files(paths: &Paths) -> Vec<(PathBuf, Vec<u8>)> {
+    let mut files: Vec<(PathBuf, Vec<u8>)> = COLOR_IMAGES
+        .iter()
+        .map(|(color, image)| {
+            (
+                paths.image_directory.join(format!("{color}.png")),
+                image.to_vec(),
+            )
+        })
+        .collect();
+    // OBS needs an image to pick before the first run
+    files.push((paths.color_image.clone(), NONE_IMAGE.to_vec()));
+    files.push((paths.lower_third.clone(), Vec::new()));
+    files.push((overlay_path(paths), OVERLAY.as_bytes().to_vec()));
+    files
+}
+
+fn starter_settings(data_directory: Option<&Path>) -> Vec<u8> {
+    let mut settings = String::from(
+        "# see README.MD for every setting\n\
+         tradition = \"lutheran\"\n\
+         locale = \"en-US\"\n",
+    );
+    if let Some(data_directory) = data_directory {
+        // quoted the TOML way, Windows paths have backslashes
+        let data_directory = toml::Value::String(data_directory.display().to_string());
+        settings.push_str(&format!("\n[paths]\ndata_directory = {data_directory}\n"));
+    }
+    settings.into_bytes()
+}
+
+// `false` when it's already there
+fn create_file(path: &Path, contents: &[u8]) -> io::Result<bool> {
+    if path.exists() {
+        return Ok(false);
+    }
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    write_output(path, contents)?;
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lib_overlay::OVERLAY_FILE_NAME;
+    use crate::lib_test_directory::test_directory;
+
+    #[test]
+    fn lays_out_files_once() {
+        let directory = test_directory("init");
+        let paths = Paths::default().resolved(&directory);
+
+        for (path, contents) in expected_files(&paths) {
+            assert!(create_file(&path, &contents).unwrap());
+        }
+        assert!(directory.join("pics").join("Purple.png").is_file());
+        assert_eq!(
+            fs::read(directory.join("pics").join("Current_Color.png")).unwrap(),
+            NONE_IMAGE
+        );
+        assert!(directory.join(OVERLAY_FILE_NAME).is_file());
+
+        // nothing that's there is replaced
+        fs::write(&paths.lower_third, "October 25th, Reformation Sunday\n").unwrap();
+        for (path, contents) in expected_files(&paths) {
+            assert!(!create_file(&path, &contents).unwrap());
+        }
+        assert_eq!(
+            fs::read_to_string(&paths.lower_third).unwrap(),
+            "October 25th, Reformation Sunday\n"
+        );
+
+        fs::remove_dir_all(&directory).unwrap();
+    }
+
+    #[test]
+    fn settings_are_valid() {
+        let settings = starter_settings(Some(Path::new(r"C:\Stream\Chapel")));
+        let config: crate::lib_config::Config =
+            toml::from_str(std::str::from_utf8(&settings).unwrap()).unwrap();
+        assert_eq!(
+            config.paths.data_directory,
+            Some(PathBuf::from(r"C:\Stream\Chapel"))
+        );
+        assert!(toml::from_str::<crate::lib_config::Config>(
+            std::str::from_utf8(&starter_settings(None)).unwrap()
+        )
+        .is_ok());
+    }
+}
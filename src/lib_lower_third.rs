@@ -0,0 +1,504 @@
+use colored::Colorize;
+use dialoguer::{theme::ColorfulTheme, Input, Select};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+use church_date_updater::lib_error::Error;
+use church_date_updater::lib_template::Template;
+
+use crate::lib_config::{LowerThirdSettings, OverlayLine, StandardLine, TitleValues};
+use crate::lib_output::{write_output, write_outputs};
+
+// what a line of the lower third is, the YouTube title is made from some of them
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum LineKind {
+    ChurchDate,
+    // the text is the sermon title without quotes, the template adds them
+    SermonTitle,
+    // `{extra_text}` in the YouTube title
+    ExtraText,
+    // a standard or recurring line, only for the lower third
+    Standard,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct LowerThirdLine {
+    pub kind: LineKind,
+    pub text: String,
+}
+
+// the lines in the order they're shown
+#[derive(Default)]
+pub struct LowerThird {
+    pub lines: Vec<LowerThirdLine>,
+}
+
+impl LowerThird {
+    pub fn push(&mut self, kind: LineKind, text: String) {
+        self.lines.push(LowerThirdLine { kind, text });
+    }
+
+    // past the end is added to the end, returns where it went
+    pub fn insert(&mut self, index: usize, kind: LineKind, text: String) -> usize {
+        let index = index.min(self.lines.len());
+        self.lines.insert(index, LowerThirdLine { kind, text });
+        index
+    }
+
+    pub fn remove(&mut self, index: usize) -> Option<LowerThirdLine> {
+        (index < self.lines.len()).then(|| self.lines.remove(index))
+    }
+
+    // the line is taken out and put back in at `to`
+    pub fn move_line(&mut self, from: usize, to: usize) -> usize {
+        match self.remove(from) {
+            Some(line) => self.insert(to, line.kind, line.text),
+            None => from,
+        }
+    }
+
+    // the text of the first line of the kind
+    pub fn find(&self, kind: LineKind) -> Option<&str> {
+        self.lines
+            .iter()
+            .find(|line| line.kind == kind)
+            .map(|line| line.text.as_str())
+    }
+
+    pub fn extra_texts(&self) -> Vec<String> {
+        self.lines
+            .iter()
+            .filter(|line| line.kind == LineKind::ExtraText)
+            .map(|line| line.text.clone())
+            .collect()
+    }
+
+    // how the lines look in OBS, the sermon title gets its quotes from the template
+    // and the other lines can have placeholders like `{long_date}` too
+    pub fn texts(&self, values: &TitleValues, sermon_title_template: &Template) -> Vec<String> {
+        self.lines
+            .iter()
+            .map(|line| match line.kind {
+                LineKind::SermonTitle => TitleValues {
+                    sermon_title: &line.text,
+                    ..*values
+                }
+                .fill(sermon_title_template),
+                LineKind::ExtraText | LineKind::Standard => filled_line(&line.text, values),
+                // already filled in from the settings, or typed out
+                LineKind::ChurchDate => line.text.clone(),
+            })
+            .collect()
+    }
+
+    // what's written to `lower_data.json`, a standard line is found in the
+    // settings by its text before its placeholders were filled in
+    pub fn data(
+        &self,
+        values: &TitleValues,
+        sermon_title_template: &Template,
+        settings: &LowerThirdSettings,
+    ) -> LowerThirdData {
+        let lines = self
+            .lines
+            .iter()
+            .zip(self.texts(values, sermon_title_template))
+            .map(|(line, text)| {
+                let overlay_line = match line.kind {
+                    LineKind::Standard => settings.overlay_line(&line.text),
+                    _ => settings.overlay_line(&text),
+                };
+                OverlayLine {
+                    text,
+                    ..overlay_line
+                }
+            })
+            .collect();
+        LowerThirdData { lines }
+    }
+
+    pub fn contains(&self, text: &str) -> bool {
+        self.lines.iter().any(|line| line.text == text)
+    }
+
+    // returns the lines that weren't already there
+    pub fn add_recurring<'a>(&mut self, recurring_lines: &'a [String]) -> Vec<&'a str> {
+        let mut added = Vec::new();
+        for line in recurring_lines {
+            if !self.contains(line) {
+                self.push(LineKind::Standard, line.clone());
+                added.push(line.as_str());
+            }
+        }
+        added
+    }
+}
+
+// a line that isn't a template, like one with a single `{`, or that has a
+// placeholder that doesn't exist is shown the way it was typed
+fn filled_line(text: &str, values: &TitleValues) -> String {
+    text.parse::<Template>()
+        .ok()
+        .and_then(|template| template.render(|name| values.get(name)).ok())
+        .unwrap_or_else(|| text.to_string())
+}
+
+// `lower_data.json`, what the overlay shows and how
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+pub struct LowerThirdData {
+    pub lines: Vec<OverlayLine>,
+}
+
+impl LowerThirdData {
+    pub fn new(texts: &[String], settings: &LowerThirdSettings) -> LowerThirdData {
+        LowerThirdData {
+            lines: texts
+                .iter()
+                .map(|text| settings.overlay_line(text))
+                .collect(),
+        }
+    }
+
+    pub fn texts(&self) -> Vec<String> {
+        self.lines.iter().map(|line| line.text.clone()).collect()
+    }
+}
+
+// the plain text is kept for overlays that only read `lower_data.txt`
+pub fn write_lower_data(
+    text_path: &Path,
+    json_path: &Path,
+    data: &LowerThirdData,
+) -> Result<(), Error> {
+    let mut text = String::new();
+    for line in &data.lines {
+        text.push_str(&line.text);
+        text.push('\n');
+    }
+    let json =
+        serde_json::to_string_pretty(data).map_err(|err| Error::io(json_path, err.into()))?;
+    // an overlay reading one of them doesn't get lines the other one doesn't have
+    write_outputs(&[(text_path, text.as_bytes()), (json_path, json.as_bytes())])
+}
+
+// one line per line, there aren't any before the first one is added
+pub fn read_recurring_lines(path: &Path) -> Result<Vec<String>, Error> {
+    if !path.is_file() {
+        return Ok(Vec::new());
+    }
+    let text = fs::read_to_string(path).map_err(|err| Error::io(path, err))?;
+    Ok(text
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(String::from)
+        .collect())
+}
+
+pub fn write_recurring_lines(path: &Path, lines: &[String]) -> Result<(), Error> {
+    let mut text = String::new();
+    for line in lines {
+        text.push_str(line);
+        text.push('\n');
+    }
+    write_output(path, text.as_bytes()).map_err(|err| Error::io(path, err))
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Change {
+    Done,
+    Add,
+    AddStandard,
+    Edit,
+    Remove,
+    Move,
+    Repeat,
+    StopRepeating,
+}
+
+fn select(prompt: &str, items: &[String], default: usize) -> Result<usize, Error> {
+    Select::with_theme(&ColorfulTheme::default())
+        .with_prompt(prompt)
+        .default(default)
+        .items(items)
+        .interact()
+        .map_err(Error::Prompt)
+}
+
+// "At the top", then after each line
+fn ask_position(texts: &[String], default: usize) -> Result<usize, Error> {
+    let mut items = vec!["At the top".to_string()];
+    items.extend(texts.iter().map(|text| format!("After '{text}'")));
+    select("Where should it go?", &items, default.min(texts.len()))
+}
+
+// insert, change, remove and move lines until the lower third looks okay.
+// `texts` is how the lines look in OBS and `checked_sermon_title` checks a
+// changed sermon title. Returns whether the recurring lines were changed.
+pub fn edit_lower_third(
+    lower_third: &mut LowerThird,
+    recurring_lines: &mut Vec<String>,
+    standard_lines: &[StandardLine],
+    texts: impl Fn(&LowerThird) -> Vec<String>,
+    checked_sermon_title: impl Fn(String) -> Result<String, Error>,
+) -> Result<bool, Error> {
+    let mut recurring_changed = false;
+    loop {
+        let shown = texts(lower_third);
+        println!("The OBS lower third looks like this:");
+        for (number, text) in shown.iter().enumerate() {
+            if recurring_lines.contains(&lower_third.lines[number].text) {
+                println!("    {}. {text} {}", number + 1, "(every week)".dimmed());
+            } else {
+                println!("    {}. {text}", number + 1);
+            }
+        }
+
+        let mut changes = vec![(Change::Done, "It looks okay"), (Change::Add, "Add a line")];
+        if !standard_lines.is_empty() {
+            changes.push((Change::AddStandard, "Add a standard line"));
+        }
+        if !lower_third.lines.is_empty() {
+            changes.push((Change::Edit, "Change a line"));
+            changes.push((Change::Remove, "Remove a line"));
+        }
+        if lower_third.lines.len() > 1 {
+            changes.push((Change::Move, "Move a line"));
+        }
+        let repeatable: Vec<usize> = (0..lower_third.lines.len())
+            .filter(|index| {
+                let line = &lower_third.lines[*index];
+                matches!(line.kind, LineKind::ExtraText | LineKind::Standard)
+                    && !recurring_lines.contains(&line.text)
+            })
+            .collect();
+        if !repeatable.is_empty() {
+            changes.push((Change::Repeat, "Show a line every week"));
+        }
+        if !recurring_lines.is_empty() {
+            changes.push((Change::StopRepeating, "Stop showing a line every week"));
+        }
+        let items: Vec<String> = changes.iter().map(|(_, item)| item.to_string()).collect();
+        let change = changes[select("What would you like to change?", &items, 0)?].0;
+
+        match change {
+            Change::Done => return Ok(recurring_changed),
+            Change::Add => {
+                let text: String = Input::with_theme(&ColorfulTheme::default())
+                    .with_prompt("Okay, what would you like to add?")
+                    .interact_text()
+                    .map_err(Error::Prompt)?;
+                let index = ask_position(&shown, shown.len())?;
+                lower_third.insert(index, LineKind::ExtraText, text);
+            }
+            Change::AddStandard => {
+                let items: Vec<String> = standard_lines
+                    .iter()
+                    .map(|standard_line| standard_line.text().to_string())
+                    .collect();
+                let selection = select("Which one?", &items, 0)?;
+                let index = ask_position(&shown, shown.len())?;
+                lower_third.insert(index, LineKind::Standard, items[selection].clone());
+            }
+            Change::Edit => {
+                let index = select("Which line?", &shown, 0)?;
+                let line = lower_third.lines[index].clone();
+                let text: String = Input::with_theme(&ColorfulTheme::default())
+                    .with_prompt("What should it be?")
+                    .with_initial_text(line.text)
+                    .interact_text()
+                    .map_err(Error::Prompt)?;
+                lower_third.lines[index].text = if line.kind == LineKind::SermonTitle {
+                    checked_sermon_title(text)?
+                } else {
+                    text
+                };
+            }
+            Change::Remove => {
+                let index = select("Which line?", &shown, 0)?;
+                if let Some(line) = lower_third.remove(index) {
+                    println!("Okay, removing '{}'.", shown[index]);
+                    if line.kind == LineKind::SermonTitle {
+                        println!("Okay, not using a sermon title.");
+                    }
+                }
+            }
+            Change::Move => {
+                let from = select("Which line?", &shown, 0)?;
+                let mut others = shown.clone();
+                others.remove(from);
+                let to = ask_position(&others, from)?;
+                lower_third.move_line(from, to);
+            }
+            Change::Repeat => {
+                let items: Vec<String> = repeatable
+                    .iter()
+                    .map(|index| shown[*index].clone())
+                    .collect();
+                let selection = select("Which line?", &items, 0)?;
+                let text = lower_third.lines[repeatable[selection]].text.clone();
+                println!("Okay, '{text}' will be added every week.");
+                recurring_lines.push(text);
+                recurring_changed = true;
+            }
+            Change::StopRepeating => {
+                let selection = select("Which line?", recurring_lines, 0)?;
+                let text = recurring_lines.remove(selection);
+                println!("Okay, '{text}' won't be added after this week.");
+                recurring_changed = true;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lib_config::Locale;
+    use crate::lib_test_directory::test_directory;
+    use chrono::NaiveDate;
+    use church_date_updater::lib_church_date::{Color, Season};
+
+    fn lower_third(texts: &[&str]) -> LowerThird {
+        let mut lower_third = LowerThird::default();
+        for text in texts {
+            lower_third.push(LineKind::ExtraText, text.to_string());
+        }
+        lower_third
+    }
+
+    fn texts(lower_third: &LowerThird) -> Vec<&str> {
+        lower_third
+            .lines
+            .iter()
+            .map(|line| line.text.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn changing_lines() {
+        let mut lines = lower_third(&["a", "b", "c"]);
+        assert_eq!(lines.insert(1, LineKind::SermonTitle, "s".to_string()), 1);
+        assert_eq!(lines.insert(9, LineKind::Standard, "z".to_string()), 4);
+        assert_eq!(texts(&lines), ["a", "s", "b", "c", "z"]);
+
+        assert_eq!(lines.move_line(4, 0), 0);
+        assert_eq!(lines.move_line(1, 3), 3);
+        assert_eq!(texts(&lines), ["z", "s", "b", "a", "c"]);
+
+        assert_eq!(lines.remove(5), None);
+        assert_eq!(lines.remove(1).unwrap().kind, LineKind::SermonTitle);
+        assert_eq!(lines.find(LineKind::SermonTitle), None);
+        assert_eq!(lines.find(LineKind::Standard), Some("z"));
+        // standard lines aren't used in the YouTube title
+        assert_eq!(lines.extra_texts(), ["b", "a", "c"]);
+    }
+
+    #[test]
+    fn placeholders_in_lines() {
+        let mut lines = LowerThird::default();
+        lines.push(LineKind::ChurchDate, "{long_date}".to_string());
+        lines.push(LineKind::SermonTitle, "Here I Stand".to_string());
+        lines.push(LineKind::ExtraText, "Bulletin for {long_date}".to_string());
+        lines.push(LineKind::Standard, "Wear {color} today".to_string());
+        lines.push(LineKind::Standard, "Giving :-{".to_string());
+        lines.push(LineKind::ExtraText, "{not_a_placeholder}".to_string());
+
+        let values = TitleValues {
+            date: NaiveDate::from_ymd_opt(2026, 10, 25).unwrap(),
+            locale: Locale::EnUs,
+            church_date: "Reformation Sunday",
+            observance_short: "Reformation Sunday",
+            observance_long: "Reformation Sunday",
+            season: Season::Pentecost,
+            color: Color::Red,
+            sermon_title: "",
+            typographic_quotes: false,
+            preacher: "",
+            extra_texts: &[],
+        };
+        assert_eq!(
+            lines.texts(&values, &"\"{sermon_title}\"".parse().unwrap()),
+            [
+                "{long_date}",
+                "\"Here I Stand\"",
+                "Bulletin for October 25th",
+                "Wear Red today",
+                "Giving :-{",
+                "{not_a_placeholder}",
+            ]
+        );
+
+        // a standard line is shown the way it says, after it's filled in too
+        let settings: LowerThirdSettings = toml::from_str(
+            r#"standard_lines = [{ text = "Wear {color} today", start = "10:45", style = "small" }]"#,
+        )
+        .unwrap();
+        let data = lines.data(&values, &"\"{sermon_title}\"".parse().unwrap(), &settings);
+        assert_eq!(data.lines[3].text, "Wear Red today");
+        assert_eq!(data.lines[3].start.unwrap().to_string(), "10:45:00");
+        assert_eq!(data.lines[3].style.as_deref(), Some("small"));
+        assert_eq!(data.lines[3].duration_seconds, Some(60));
+        assert_eq!(data.lines[2].style, None);
+    }
+
+    #[test]
+    fn lower_third_data() {
+        let directory = test_directory("lower-third");
+        let text_path = directory.join("lower_data.txt");
+        let json_path = directory.join("lower_data.json");
+
+        let settings: LowerThirdSettings = toml::from_str(
+            r#"
+            duration_seconds = 30
+            standard_lines = [{ text = "Giving", start = "10:45", style = "small" }]
+            "#,
+        )
+        .unwrap();
+        let texts = vec![
+            "October 25th, Reformation Sunday".to_string(),
+            "Giving".to_string(),
+        ];
+        let written = LowerThirdData::new(&texts, &settings);
+        write_lower_data(&text_path, &json_path, &written).unwrap();
+
+        assert_eq!(
+            fs::read_to_string(&text_path).unwrap(),
+            "October 25th, Reformation Sunday\nGiving\n"
+        );
+        let data: LowerThirdData =
+            serde_json::from_str(&fs::read_to_string(&json_path).unwrap()).unwrap();
+        assert_eq!(data, written);
+        assert_eq!(data.lines[0].duration_seconds, Some(30));
+        assert_eq!(data.lines[0].start, None);
+        assert_eq!(data.lines[1].start.unwrap().to_string(), "10:45:00");
+        assert_eq!(data.lines[1].style.as_deref(), Some("small"));
+
+        fs::remove_dir_all(&directory).unwrap();
+    }
+
+    #[test]
+    fn recurring_lines() {
+        let directory = test_directory("recurring");
+        let path = directory.join("recurring_lines.txt");
+        assert!(read_recurring_lines(&path).unwrap().is_empty());
+
+        let recurring = vec![
+            "Welcome!".to_string(),
+            "Giving: example.org/give".to_string(),
+        ];
+        write_recurring_lines(&path, &recurring).unwrap();
+        assert_eq!(read_recurring_lines(&path).unwrap(), recurring);
+
+        let mut lines = lower_third(&["Welcome!"]);
+        assert_eq!(
+            lines.add_recurring(&recurring),
+            ["Giving: example.org/give"]
+        );
+        assert_eq!(lines.lines[1].kind, LineKind::Standard);
+        assert!(lines.add_recurring(&recurring).is_empty());
+
+        fs::remove_dir_all(&directory).unwrap();
+    }
+}
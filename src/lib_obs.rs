@@ -0,0 +1,382 @@
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::path::{self, Path};
+use std::time::Duration;
+use tungstenite::{Message, WebSocket};
+
+use church_date_updater::lib_error::Error;
+
+use crate::lib_config::ObsSettings;
+
+// OBS WebSocket 5, see
+// https://github.com/obsproject/obs-websocket/blob/master/docs/generated/protocol.md
+const RPC_VERSION: u64 = 1;
+
+// OBS answers right away, it's not open when it doesn't
+const TIMEOUT: Duration = Duration::from_secs(5);
+
+// message types, `op` in every message
+const HELLO: u64 = 0;
+const IDENTIFY: u64 = 1;
+const IDENTIFIED: u64 = 2;
+const REQUEST: u64 = 6;
+const REQUEST_RESPONSE: u64 = 7;
+
+pub struct ObsClient {
+    socket: WebSocket<TcpStream>,
+    next_request_id: u64,
+}
+
+impl ObsClient {
+    // connects and signs in with the password when OBS asks for one
+    pub fn connect(settings: &ObsSettings) -> Result<ObsClient, Error> {
+        let address = (settings.host.as_str(), settings.port)
+            .to_socket_addrs()
+            .ok()
+            .and_then(|mut addresses| addresses.next())
+            .ok_or_else(|| Error::Obs(format!("'{}' isn't a host", settings.host)))?;
+        let unreachable = |err: std::io::Error| {
+            Error::Obs(format!(
+                "OBS isn't reachable at {}:{}: {err}",
+                settings.host, settings.port
+            ))
+        };
+        let stream = TcpStream::connect_timeout(&address, TIMEOUT).map_err(unreachable)?;
+        stream
+            .set_read_timeout(Some(TIMEOUT))
+            .map_err(unreachable)?;
+        stream
+            .set_write_timeout(Some(TIMEOUT))
+            .map_err(unreachable)?;
+        // `[::1]:4455`, IPv6 addresses need the brackets in a URL
+        let url = format!("ws://{address}");
+        let (socket, _) = tungstenite::client(url, stream)
+            .map_err(|err| Error::Obs(format!("OBS's WebSocket server didn't answer: {err}")))?;
+
+        let mut client = ObsClient {
+            socket,
+            next_request_id: 1,
+        };
+        let hello = client.receive(HELLO)?;
+        let mut identify = json!({
+            "rpcVersion": RPC_VERSION,
+            // only requests, no events
+            "eventSubscriptions": 0,
+        });
+        if let Some(authentication) = hello.get("authentication") {
+            let challenge = authentication["challenge"].as_str().unwrap_or_default();
+            let salt = authentication["salt"].as_str().unwrap_or_default();
+            identify["authentication"] =
+                Value::String(authentication_string(&settings.password, salt, challenge));
+        }
+        client.send(IDENTIFY, identify)?;
+        client.receive(IDENTIFIED)?;
+        Ok(client)
+    }
+
+    fn send(&mut self, op: u64, data: Value) -> Result<(), Error> {
+        let message = json!({ "op": op, "d": data }).to_string();
+        self.socket
+            .send(Message::text(message))
+            .map_err(|err| Error::Obs(format!("Unable to send to OBS: {err}")))
+    }
+
+    // the data of the next message of the type, the others are skipped
+    fn receive(&mut self, op: u64) -> Result<Value, Error> {
+        loop {
+            let message = self
+                .socket
+                .read()
+                .map_err(|err| Error::Obs(format!("OBS closed the connection: {err}")))?;
+            let text = match message {
+                Message::Text(text) => text,
+                // OBS says why, like "Authentication failed."
+                Message::Close(Some(frame)) => {
+                    return Err(Error::Obs(format!(
+                        "OBS closed the connection: {}",
+                        frame.reason
+                    )))
+                }
+                _ => continue,
+            };
+            let mut message: Value = serde_json::from_str(&text)
+                .map_err(|err| Error::Obs(format!("OBS sent something unexpected: {err}")))?;
+            if message["op"] == op {
+                return Ok(message["d"].take());
+            }
+        }
+    }
+
+    pub fn request(&mut self, request_type: &str, request_data: Value) -> Result<Value, Error> {
+        let request_id = self.next_request_id.to_string();
+        self.next_request_id += 1;
+        self.send(
+            REQUEST,
+            json!({
+                "requestType": request_type,
+                "requestId": request_id,
+                "requestData": request_data,
+            }),
+        )?;
+        loop {
+            let mut response = self.receive(REQUEST_RESPONSE)?;
+            if response["requestId"] != request_id.as_str() {
+                continue;
+            }
+            let status = &response["requestStatus"];
+            if status["result"] != true {
+                return Err(Error::Obs(format!(
+                    "{request_type} didn't work: {}",
+                    status["comment"].as_str().unwrap_or("no reason was given")
+                )));
+            }
+            return Ok(response["responseData"].take());
+        }
+    }
+
+    pub fn set_text(&mut self, source: &str, text: &str) -> Result<(), Error> {
+        self.request(
+            "SetInputSettings",
+            json!({ "inputName": source, "inputSettings": { "text": text } }),
+        )?;
+        Ok(())
+    }
+
+    // OBS needs the whole path
+    pub fn set_image(&mut self, source: &str, file: &Path) -> Result<(), Error> {
+        let file = path::absolute(file).map_err(|err| Error::io(file, err))?;
+        self.request(
+            "SetInputSettings",
+            json!({ "inputName": source, "inputSettings": { "file": file } }),
+        )?;
+        Ok(())
+    }
+
+    // the same as "Refresh cache of current page" in the source's properties
+    pub fn refresh_browser(&mut self, source: &str) -> Result<(), Error> {
+        self.request(
+            "PressInputPropertiesButton",
+            json!({ "inputName": source, "propertyName": "refreshnocache" }),
+        )?;
+        Ok(())
+    }
+}
+
+// base64(sha256(base64(sha256(password + salt)) + challenge))
+fn authentication_string(password: &str, salt: &str, challenge: &str) -> String {
+    let secret = BASE64.encode(Sha256::digest(format!("{password}{salt}")));
+    BASE64.encode(Sha256::digest(format!("{secret}{challenge}")))
+}
+
+// the sources that aren't in the settings, or weren't changed, are left alone
+pub fn update_obs(
+    settings: &ObsSettings,
+    lower_third: Option<&[String]>,
+    color_image: Option<&Path>,
+) -> Result<(), Error> {
+    let mut obs = ObsClient::connect(settings)?;
+    if let (Some(source), Some(lower_third)) = (&settings.text_source, lower_third) {
+        obs.set_text(source, &lower_third.join("\n"))?;
+    }
+    if let (Some(source), Some(color_image)) = (&settings.image_source, color_image) {
+        obs.set_image(source, color_image)?;
+    }
+    if let Some(source) = &settings.browser_source {
+        obs.refresh_browser(source)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::TcpListener;
+    use std::thread::{self, JoinHandle};
+
+    const SALT: &str = "lM1GncleQOaCu9lT1yeUZhFYnqhsLLP1G5lAGo3ixaI=";
+    const CHALLENGE: &str = "+IxH4CnCiqpX1rM9scsNynZzbOe4KhDeYcTNS3PDaeY=";
+
+    // answers like OBS with the password "supersecretpassword", and gives back
+    // the requests it was sent
+    fn mock_obs(host: &str) -> (ObsSettings, JoinHandle<Vec<Value>>) {
+        let listener = TcpListener::bind((host, 0)).unwrap();
+        let settings = ObsSettings {
+            host: host.to_string(),
+            port: listener.local_addr().unwrap().port(),
+            ..ObsSettings::default()
+        };
+        let obs = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut socket = tungstenite::accept(stream).unwrap();
+            let send = |socket: &mut WebSocket<TcpStream>, op: u64, data: Value| {
+                let message = json!({ "op": op, "d": data }).to_string();
+                socket.send(Message::text(message)).unwrap();
+            };
+            let receive = |socket: &mut WebSocket<TcpStream>| -> Option<Value> {
+                let message = socket.read().ok()?;
+                serde_json::from_str(message.to_text().ok()?).ok()
+            };
+
+            send(
+                &mut socket,
+                HELLO,
+                json!({
+                    "obsWebSocketVersion": "5.5.0",
+                    "rpcVersion": 1,
+                    "authentication": { "challenge": CHALLENGE, "salt": SALT },
+                }),
+            );
+            let identify = receive(&mut socket).unwrap();
+            assert_eq!(identify["op"], IDENTIFY);
+            if identify["d"]["authentication"] != "1Ct943GAT+6YQUUX47Ia/ncufilbe6+oD6lY+5kaCu4=" {
+                let _ = socket.close(Some(tungstenite::protocol::CloseFrame {
+                    code: 4009.into(),
+                    reason: "Authentication failed.".into(),
+                }));
+                let _ = socket.flush();
+                return Vec::new();
+            }
+            send(
+                &mut socket,
+                IDENTIFIED,
+                json!({ "negotiatedRpcVersion": 1 }),
+            );
+
+            let mut requests = Vec::new();
+            while let Some(request) = receive(&mut socket) {
+                let data = &request["d"];
+                // an event OBS could send in between
+                send(&mut socket, 5, json!({ "eventType": "ExitStarted" }));
+                let found = data["requestData"]["inputName"] != "Missing";
+                send(
+                    &mut socket,
+                    REQUEST_RESPONSE,
+                    json!({
+                        "requestType": data["requestType"],
+                        "requestId": data["requestId"],
+                        "requestStatus": if found {
+                            json!({ "result": true, "code": 100 })
+                        } else {
+                            json!({
+                                "result": false,
+                                "code": 600,
+                                "comment": "No source was found by the name of `Missing`.",
+                            })
+                        },
+                    }),
+                );
+                requests.push(data.clone());
+            }
+            requests
+        });
+        (settings, obs)
+    }
+
+    #[test]
+    fn sets_the_sources() {
+        let (settings, obs) = mock_obs("127.0.0.1");
+        let settings = ObsSettings {
+            password: "supersecretpassword".to_string(),
+            text_source: Some("Lower third".to_string()),
+            image_source: Some("Color".to_string()),
+            browser_source: Some("Overlay".to_string()),
+            ..settings
+        };
+        let lower_third = [
+            "October 25th, Reformation Sunday".to_string(),
+            "\"Here I Stand\"".to_string(),
+        ];
+        update_obs(
+            &settings,
+            Some(&lower_third),
+            Some(Path::new("pics/Red.png")),
+        )
+        .unwrap();
+
+        let requests = obs.join().unwrap();
+        assert_eq!(requests.len(), 3);
+        assert_eq!(requests[0]["requestType"], "SetInputSettings");
+        assert_eq!(
+            requests[0]["requestData"],
+            json!({
+                "inputName": "Lower third",
+                "inputSettings": { "text": "October 25th, Reformation Sunday\n\"Here I Stand\"" },
+            })
+        );
+        assert_eq!(requests[1]["requestData"]["inputName"], "Color");
+        let file = requests[1]["requestData"]["inputSettings"]["file"]
+            .as_str()
+            .unwrap();
+        assert!(Path::new(file).is_absolute());
+        assert!(file.ends_with("Red.png"));
+        assert_eq!(requests[2]["requestType"], "PressInputPropertiesButton");
+        assert_eq!(requests[2]["requestData"]["propertyName"], "refreshnocache");
+        // every request has its own id
+        assert_ne!(requests[0]["requestId"], requests[1]["requestId"]);
+    }
+
+    #[test]
+    fn only_the_sources_that_changed() {
+        let (settings, obs) = mock_obs("127.0.0.1");
+        let settings = ObsSettings {
+            password: "supersecretpassword".to_string(),
+            text_source: Some("Lower third".to_string()),
+            image_source: Some("Color".to_string()),
+            ..settings
+        };
+        update_obs(&settings, None, Some(Path::new("pics/Red.png"))).unwrap();
+        let requests = obs.join().unwrap();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0]["requestData"]["inputName"], "Color");
+    }
+
+    #[test]
+    fn ipv6_hosts() {
+        let (settings, obs) = mock_obs("::1");
+        let settings = ObsSettings {
+            password: "supersecretpassword".to_string(),
+            browser_source: Some("Overlay".to_string()),
+            ..settings
+        };
+        update_obs(&settings, None, None).unwrap();
+        assert_eq!(obs.join().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn errors() {
+        let (settings, obs) = mock_obs("127.0.0.1");
+        let settings = ObsSettings {
+            password: "wrong".to_string(),
+            ..settings
+        };
+        let Err(err) = ObsClient::connect(&settings) else {
+            panic!("the password is wrong");
+        };
+        assert_eq!(
+            err.to_string(),
+            "Unable to update OBS: OBS closed the connection: Authentication failed."
+        );
+        obs.join().unwrap();
+
+        let (settings, obs) = mock_obs("127.0.0.1");
+        let settings = ObsSettings {
+            password: "supersecretpassword".to_string(),
+            text_source: Some("Missing".to_string()),
+            ..settings
+        };
+        let Err(err) = update_obs(&settings, Some(&[]), None) else {
+            panic!("there isn't a source named 'Missing'");
+        };
+        assert_eq!(
+            err.to_string(),
+            "Unable to update OBS: SetInputSettings didn't work: No source was found by the name of `Missing`."
+        );
+        obs.join().unwrap();
+
+        // nothing is listening on the port anymore
+        assert!(matches!(ObsClient::connect(&settings), Err(Error::Obs(_))));
+    }
+}
@@ -0,0 +1,175 @@
+use std::ffi::OsString;
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use church_date_updater::lib_error::Error;
+
+// Files OBS is watching are never half written: the new contents go to a
+// temporary file next to the output, which is then renamed over it. Renaming
+// replaces the file in one step and gives it a new modification time, which is
+// what OBS looks at to notice the change.
+
+// `lower_data.txt` is kept as `lower_data.txt.bak`
+pub fn backup_path(path: &Path) -> PathBuf {
+    with_suffix(path, ".bak")
+}
+
+fn with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut file_name = path.file_name().map(OsString::from).unwrap_or_default();
+    file_name.push(suffix);
+    path.with_file_name(file_name)
+}
+
+pub fn write_output(path: &Path, contents: &[u8]) -> io::Result<()> {
+    let temporary_path = write_temporary(path, contents)?;
+    replace_output(path, &temporary_path)
+}
+
+// files that are read together, like `lower_data.txt` and `lower_data.json`:
+// every one is written before any of them are replaced, so one that can't be
+// written leaves all of them the way they were
+pub fn write_outputs(outputs: &[(&Path, &[u8])]) -> Result<(), Error> {
+    let mut temporary_paths = Vec::new();
+    for (path, contents) in outputs {
+        match write_temporary(path, contents) {
+            Ok(temporary_path) => temporary_paths.push(temporary_path),
+            Err(err) => {
+                for temporary_path in &temporary_paths {
+                    let _ = fs::remove_file(temporary_path);
+                }
+                return Err(Error::io(*path, err));
+            }
+        }
+    }
+    for (replaced, ((path, _), temporary_path)) in outputs.iter().zip(&temporary_paths).enumerate()
+    {
+        if let Err(err) = replace_output(path, temporary_path) {
+            // the ones after it aren't used
+            for temporary_path in &temporary_paths[replaced + 1..] {
+                let _ = fs::remove_file(temporary_path);
+            }
+            return Err(Error::io(*path, err));
+        }
+    }
+    Ok(())
+}
+
+fn write_temporary(path: &Path, contents: &[u8]) -> io::Result<PathBuf> {
+    // in the same folder, renaming only replaces the file in one step on the
+    // same drive
+    let temporary_path = with_suffix(path, ".tmp");
+
+    let written = File::create(&temporary_path).and_then(|mut file| {
+        file.write_all(contents)?;
+        file.sync_all()
+    });
+    match written {
+        Ok(()) => Ok(temporary_path),
+        Err(err) => {
+            let _ = fs::remove_file(&temporary_path);
+            Err(err)
+        }
+    }
+}
+
+fn replace_output(path: &Path, temporary_path: &Path) -> io::Result<()> {
+    // the output stays in place while the backup is made
+    if path.is_file() {
+        if let Err(err) = fs::copy(path, backup_path(path)) {
+            let _ = fs::remove_file(temporary_path);
+            return Err(err);
+        }
+    }
+
+    if let Err(err) = fs::rename(temporary_path, path) {
+        let _ = fs::remove_file(temporary_path);
+        return Err(err);
+    }
+    Ok(())
+}
+
+pub fn copy_output(from: &Path, to: &Path) -> io::Result<()> {
+    write_output(to, &fs::read(from)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lib_test_directory::test_directory;
+
+    #[test]
+    fn writes_and_keeps_a_backup() {
+        let directory = test_directory("output");
+        let path = directory.join("lower_data.txt");
+
+        write_output(&path, b"first\n").unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "first\n");
+        assert!(!backup_path(&path).exists());
+
+        write_output(&path, b"second\n").unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "second\n");
+        assert_eq!(fs::read_to_string(backup_path(&path)).unwrap(), "first\n");
+        assert!(!with_suffix(&path, ".tmp").exists());
+
+        fs::remove_dir_all(&directory).unwrap();
+    }
+
+    #[test]
+    fn copies() {
+        let directory = test_directory("copy");
+        let image = directory.join("Green.png");
+        let current = directory.join("Current_Color.png");
+        fs::write(&image, b"green").unwrap();
+
+        copy_output(&image, &current).unwrap();
+        assert_eq!(fs::read(&current).unwrap(), b"green");
+
+        // a missing image leaves the current one alone
+        assert!(copy_output(&directory.join("Pink.png"), &current).is_err());
+        assert_eq!(fs::read(&current).unwrap(), b"green");
+
+        fs::remove_dir_all(&directory).unwrap();
+    }
+
+    #[test]
+    fn outputs_that_belong_together() {
+        let directory = test_directory("together");
+        let text_path = directory.join("lower_data.txt");
+        let json_path = directory.join("lower_data.json");
+        write_outputs(&[(&text_path, b"first\n"), (&json_path, b"[\"first\"]")]).unwrap();
+        assert_eq!(fs::read_to_string(&text_path).unwrap(), "first\n");
+        assert_eq!(fs::read_to_string(&json_path).unwrap(), "[\"first\"]");
+
+        // the text isn't replaced when the JSON can't be written
+        let missing_path = directory.join("missing").join("lower_data.json");
+        assert!(matches!(
+            write_outputs(&[(&text_path, b"second\n"), (&missing_path, b"[\"second\"]")]),
+            Err(Error::Io { path, .. }) if path == missing_path
+        ));
+        assert_eq!(fs::read_to_string(&text_path).unwrap(), "first\n");
+        assert!(!with_suffix(&text_path, ".tmp").exists());
+
+        // a folder can't be replaced, nothing is left behind when the first or
+        // the second output can't be
+        let folder_path = directory.join("folder");
+        fs::create_dir_all(folder_path.join("inside")).unwrap();
+        assert!(write_outputs(&[(&folder_path, b"first"), (&json_path, b"[]")]).is_err());
+        assert_eq!(fs::read_to_string(&json_path).unwrap(), "[\"first\"]");
+        assert!(write_outputs(&[(&text_path, b"third\n"), (&folder_path, b"second")]).is_err());
+        assert_eq!(fs::read_to_string(&text_path).unwrap(), "third\n");
+        for path in [&text_path, &json_path, &folder_path] {
+            assert!(!with_suffix(path, ".tmp").exists());
+        }
+
+        fs::remove_dir_all(&directory).unwrap();
+    }
+
+    #[test]
+    fn missing_folder() {
+        let directory = test_directory("missing");
+        let path = directory.join("missing").join("lower_data.txt");
+        assert!(write_output(&path, b"text").is_err());
+        fs::remove_dir_all(&directory).unwrap();
+    }
+}
@@ -0,0 +1,252 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use church_date_updater::lib_error::Error;
+use church_date_updater::lib_template::{Template, TemplateError, TemplateValue};
+
+use crate::lib_config::{Config, OverlayLayout, OverlaySettings, Paths};
+use crate::lib_output::write_output;
+
+// `{text_color}`, `{accent_color}`, `{background_color}`, `{font}` and `{color}`,
+// with a section for each layout: `{#fly_in}`, `{#banner}` and `{#slide}`
+const TEMPLATE: &str = include_str!("../church_date_template.html");
+
+// the overlay reads the lower third from the folder it's in
+pub const OVERLAY_FILE_NAME: &str = "church_date.html";
+
+// in every overlay that's made, one without it was changed by hand
+const MADE_BY: &str = "made by Church Date Updater";
+
+pub fn overlay_path(paths: &Paths) -> PathBuf {
+    paths
+        .lower_third
+        .parent()
+        .unwrap_or(Path::new(""))
+        .join(OVERLAY_FILE_NAME)
+}
+
+// CSS colors for a liturgical color, one of `COLOR_NAMES`
+#[derive(PartialEq, Eq, Debug)]
+pub struct Theme {
+    pub text: &'static str,
+    // the bars around the text
+    pub accent: &'static str,
+    pub background: &'static str,
+}
+
+pub fn theme(color_name: &str) -> Theme {
+    let (text, accent, background) = match color_name {
+        // gold on white
+        "White" => ("#4a3b1f", "#c9a227", "#fdfbf5"),
+        "Green" => ("#1f4d2b", "#2e7d32", "#eef6ee"),
+        "Purple" => ("#3f1f5c", "#6a1b9a", "#f3eef8"),
+        "Red" => ("#7a1010", "#c62828", "#fbeeee"),
+        "Yellow" => ("#5c4700", "#f9a825", "#fffbe6"),
+        "Blue" => ("#0d2f5c", "#1565c0", "#edf3fb"),
+        // light text, dark text wouldn't be seen
+        "Black" => ("#f5f5f5", "#9e9e9e", "#111111"),
+        // the brown the overlay has always had
+        _ => ("#5f3915", "#5f3915", "#f7f1ea"),
+    };
+    Theme {
+        text,
+        accent,
+        background,
+    }
+}
+
+// the built-in template, or the one in the settings
+pub fn overlay_template(paths: &Paths) -> Result<Template, Error> {
+    let Some(path) = &paths.overlay_template else {
+        return Ok(TEMPLATE
+            .parse()
+            .expect("the built-in overlay template is valid"));
+    };
+    let text = fs::read_to_string(path).map_err(|err| Error::io(path, err))?;
+    text.parse().map_err(|err| template_error(path, err))
+}
+
+fn template_error(path: &Path, err: TemplateError) -> Error {
+    Error::Settings(format!(
+        "The overlay template '{}' can't be used: {err}",
+        path.display()
+    ))
+}
+
+pub fn render_overlay(
+    template: &Template,
+    settings: &OverlaySettings,
+    color_name: &str,
+) -> Result<String, TemplateError> {
+    let theme = theme(color_name);
+    // the layout's section is the one that isn't empty
+    let layout = |layout: OverlayLayout| {
+        let shown = if settings.layout == layout { "yes" } else { "" };
+        Some(TemplateValue::Text(shown.to_string()))
+    };
+    template.render(|name| match name {
+        "text_color" => Some(TemplateValue::Text(theme.text.to_string())),
+        "accent_color" => Some(TemplateValue::Text(theme.accent.to_string())),
+        "background_color" => Some(TemplateValue::Text(theme.background.to_string())),
+        "font" => Some(TemplateValue::Text(settings.font.clone())),
+        "color" => Some(TemplateValue::Text(color_name.to_string())),
+        "fly_in" => layout(OverlayLayout::FlyIn),
+        "banner" => layout(OverlayLayout::Banner),
+        "slide" => layout(OverlayLayout::Slide),
+        _ => None,
+    })
+}
+
+// the overlay is made again each run, so it's in the color of the day, `false`
+// when it was changed by hand and is left alone
+pub fn write_overlay(config: &Config, color_name: &str) -> Result<bool, Error> {
+    let paths = &config.paths;
+    let template = overlay_template(paths)?;
+    let overlay = render_overlay(&template, &config.overlay, color_name).map_err(|err| {
+        template_error(
+            paths
+                .overlay_template
+                .as_deref()
+                .unwrap_or(Path::new(OVERLAY_FILE_NAME)),
+            err,
+        )
+    })?;
+    // templates of your own are marked too, or it wouldn't be made again
+    let overlay = if overlay.contains(MADE_BY) {
+        overlay
+    } else {
+        format!("{overlay}\n<!-- {MADE_BY} -->\n")
+    };
+    let path = overlay_path(paths);
+    match fs::read_to_string(&path) {
+        // left alone when it's the same, so an open overlay isn't loaded again
+        Ok(current) if current == overlay => return Ok(true),
+        Ok(current) if !current.contains(MADE_BY) => return Ok(false),
+        _ => {}
+    }
+    write_output(&path, overlay.as_bytes()).map_err(|err| Error::io(path, err))?;
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lib_cli_args::COLOR_NAMES;
+    use crate::lib_test_directory::test_directory;
+
+    fn render(layout: OverlayLayout, color_name: &str) -> String {
+        let settings = OverlaySettings {
+            layout,
+            ..OverlaySettings::default()
+        };
+        render_overlay(&TEMPLATE.parse().unwrap(), &settings, color_name).unwrap()
+    }
+
+    #[test]
+    fn every_color_has_a_theme() {
+        for color_name in COLOR_NAMES {
+            let theme = theme(color_name);
+            let overlay = render(OverlayLayout::FlyIn, color_name);
+            assert!(overlay.contains(&format!("color: {};", theme.text)));
+            assert!(overlay.contains(&format!("solid {};", theme.accent)));
+            assert!(overlay.contains(&format!("background: {}cc;", theme.background)));
+        }
+        assert_ne!(theme("Purple"), theme("None"));
+        assert_eq!(theme("None").text, "#5f3915");
+    }
+
+    #[test]
+    fn layouts() {
+        let fly_in = render(OverlayLayout::FlyIn, "Green");
+        assert!(fly_in.contains("translateX(100%)"));
+        assert!(fly_in.contains("<div id=\"line\">"));
+        assert!(!fly_in.contains("<div id=\"slide\">"));
+        assert!(!fly_in.contains("bottom: 6%"));
+
+        let banner = render(OverlayLayout::Banner, "Green");
+        assert!(banner.contains("bottom: 6%"));
+        assert!(!banner.contains("translateX(100%)"));
+
+        let slide = render(OverlayLayout::Slide, "Green");
+        assert!(slide.contains("<div id=\"slide\">"));
+        assert!(slide.contains("function showAll()"));
+        // the times of the lines are checked even when nothing is sent
+        assert!(slide.contains("setInterval(showAll, 5000);"));
+        assert!(!slide.contains("function showNext()"));
+        // the braces of the CSS and JavaScript are kept
+        assert!(slide.contains("function showAll() {\n"));
+    }
+
+    #[test]
+    fn the_overlay_in_the_repository_is_the_default() {
+        // `church_date.html` is what `init` lays out, before the first run
+        assert_eq!(
+            render(OverlayLayout::FlyIn, "None"),
+            include_str!("../church_date.html")
+        );
+    }
+
+    #[test]
+    fn templates_of_your_own() {
+        let directory = test_directory("overlay");
+        let mut paths = Paths::default().resolved(&directory);
+        let config_template = directory.join("overlay.html");
+        paths.overlay_template = Some(config_template.clone());
+
+        fs::write(
+            &config_template,
+            "<p style=\"color: {text_color}\">{color}</p>",
+        )
+        .unwrap();
+        let template = overlay_template(&paths).unwrap();
+        assert_eq!(
+            render_overlay(&template, &OverlaySettings::default(), "Red").unwrap(),
+            "<p style=\"color: #7a1010\">Red</p>"
+        );
+
+        // CSS braces need to be doubled
+        fs::write(&config_template, "p { color: red").unwrap();
+        assert!(matches!(overlay_template(&paths), Err(Error::Settings(_))));
+        let template = "p {{ color: {text_colour} }}".parse().unwrap();
+        assert_eq!(
+            render_overlay(&template, &OverlaySettings::default(), "Red"),
+            Err(TemplateError::UnknownName("text_colour".to_string()))
+        );
+
+        fs::remove_dir_all(&directory).unwrap();
+    }
+
+    #[test]
+    fn overlays_changed_by_hand() {
+        let directory = test_directory("hand-made");
+        let mut config = Config {
+            paths: Paths::default().resolved(&directory),
+            ..Config::default()
+        };
+        let path = overlay_path(&config.paths);
+        assert!(write_overlay(&config, "Red").unwrap());
+        assert!(write_overlay(&config, "Green").unwrap());
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            render(OverlayLayout::FlyIn, "Green")
+        );
+
+        fs::write(&path, "<p>Mine</p>").unwrap();
+        assert!(!write_overlay(&config, "Purple").unwrap());
+        assert_eq!(fs::read_to_string(&path).unwrap(), "<p>Mine</p>");
+
+        // a template without the comment is still made again
+        fs::remove_file(&path).unwrap();
+        let config_template = directory.join("overlay.html");
+        fs::write(&config_template, "<p>{color}</p>").unwrap();
+        config.paths.overlay_template = Some(config_template);
+        assert!(write_overlay(&config, "Red").unwrap());
+        assert!(write_overlay(&config, "Green").unwrap());
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "<p>Green</p>\n<!-- made by Church Date Updater -->\n"
+        );
+
+        fs::remove_dir_all(&directory).unwrap();
+    }
+}
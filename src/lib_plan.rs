@@ -0,0 +1,300 @@
+use chrono::NaiveDate;
+use serde::de::Error;
+use serde::{Deserialize, Deserializer};
+use std::fs;
+use std::path::Path;
+
+use church_date_updater::lib_church_date::ChurchDateResult;
+
+use crate::lib_cli_args::COLOR_NAMES;
+
+// one service in the plan, found by its date or, for services that are the
+// same every year, by its observance
+#[derive(Deserialize, Default, Clone, PartialEq, Eq, Debug)]
+#[serde(default, deny_unknown_fields)]
+pub struct PlannedService {
+    #[serde(deserialize_with = "toml_date")]
+    pub date: Option<NaiveDate>,
+    pub observance: Option<String>,
+    pub sermon_title: Option<String>,
+    pub preacher: Option<String>,
+    pub extra_text: Vec<String>,
+    pub color: Option<String>,
+}
+
+// both `date = 2026-10-25` and `date = "2026-10-25"`, in YAML it's always text
+fn toml_date<'de, D>(deserializer: D) -> Result<Option<NaiveDate>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let date = match toml::Value::deserialize(deserializer)? {
+        toml::Value::String(date) => date,
+        toml::Value::Datetime(date) if date.time.is_none() => date.to_string(),
+        date => return Err(D::Error::custom(format!("'{date}' isn't a date"))),
+    };
+    date.parse().map(Some).map_err(D::Error::custom)
+}
+
+#[derive(Deserialize, Default, Debug)]
+#[serde(default, deny_unknown_fields)]
+pub struct Plan {
+    #[serde(rename = "service")]
+    pub services: Vec<PlannedService>,
+}
+
+// spreadsheets can't have lists, so extra texts are separated by `|`
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct CsvRow {
+    #[serde(default)]
+    date: Option<NaiveDate>,
+    #[serde(default)]
+    observance: Option<String>,
+    #[serde(default)]
+    sermon_title: Option<String>,
+    #[serde(default)]
+    preacher: Option<String>,
+    #[serde(default)]
+    extra_text: Option<String>,
+    #[serde(default)]
+    color: Option<String>,
+}
+
+impl From<CsvRow> for PlannedService {
+    fn from(row: CsvRow) -> Self {
+        PlannedService {
+            date: row.date,
+            observance: row.observance,
+            sermon_title: row.sermon_title,
+            preacher: row.preacher,
+            extra_text: row
+                .extra_text
+                .map(|extra_text| {
+                    extra_text
+                        .split('|')
+                        .map(|line| line.trim().to_string())
+                        .filter(|line| !line.is_empty())
+                        .collect()
+                })
+                .unwrap_or_default(),
+            color: row.color,
+        }
+    }
+}
+
+impl Plan {
+    pub fn from_toml(text: &str) -> Result<Plan, String> {
+        let plan: Plan = toml::from_str(text).map_err(|err| err.to_string())?;
+        plan.checked()
+    }
+
+    pub fn from_yaml(text: &str) -> Result<Plan, String> {
+        let plan: Plan = serde_yaml::from_str(text).map_err(|err| err.to_string())?;
+        plan.checked()
+    }
+
+    pub fn from_csv(text: &str) -> Result<Plan, String> {
+        let mut reader = csv::ReaderBuilder::new()
+            .trim(csv::Trim::All)
+            .from_reader(text.as_bytes());
+        let mut services = Vec::new();
+        for row in reader.deserialize::<CsvRow>() {
+            services.push(row.map_err(|err| err.to_string())?.into());
+        }
+        Plan { services }.checked()
+    }
+
+    // colors are written like the color names in the setup
+    fn checked(mut self) -> Result<Plan, String> {
+        for service in &mut self.services {
+            if service.date.is_none() && service.observance.is_none() {
+                return Err("every service needs a date or an observance".to_string());
+            }
+            if let Some(color) = &service.color {
+                let Some(color_name) = COLOR_NAMES
+                    .iter()
+                    .find(|color_name| color_name.eq_ignore_ascii_case(color))
+                else {
+                    return Err(format!(
+                        "'{color}' isn't a color, use one of {}",
+                        COLOR_NAMES.join(", ")
+                    ));
+                };
+                service.color = Some((*color_name).to_string());
+            }
+        }
+        Ok(self)
+    }
+
+    // a service planned for the date comes before one planned for the observance
+    pub fn find(
+        &self,
+        church_date: &ChurchDateResult,
+        church_date_long: &ChurchDateResult,
+    ) -> Option<&PlannedService> {
+        let by_date = self
+            .services
+            .iter()
+            .find(|service| service.date == Some(church_date.date));
+        by_date.or_else(|| {
+            self.services.iter().find(|service| {
+                service.date.is_none()
+                    && service.observance.as_ref().is_some_and(|observance| {
+                        observance.eq_ignore_ascii_case(&church_date.text)
+                            || observance.eq_ignore_ascii_case(&church_date_long.text)
+                    })
+            })
+        })
+    }
+}
+
+// there isn't a plan when the file doesn't exist
+pub fn load_plan(path: &Path) -> Result<Option<Plan>, String> {
+    let has_extension = |name: &str| {
+        path.extension()
+            .is_some_and(|extension| extension.eq_ignore_ascii_case(name))
+    };
+    if !path.is_file() {
+        return Ok(None);
+    }
+    let text = fs::read_to_string(path)
+        .map_err(|err| format!("Unable to read '{}': {err}", path.display()))?;
+    let plan = if has_extension("csv") {
+        Plan::from_csv(&text)
+    } else if has_extension("yaml") || has_extension("yml") {
+        Plan::from_yaml(&text)
+    } else {
+        Plan::from_toml(&text)
+    };
+    plan.map(Some)
+        .map_err(|err| format!("Unable to read '{}': {err}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lib_test_directory::test_directory;
+    use church_date_updater::lib_church_date::{next_church_date, OrdinalLength, Tradition};
+
+    fn church_dates(date: &str) -> (ChurchDateResult, ChurchDateResult) {
+        let date = date.parse().unwrap();
+        (
+            next_church_date(date, OrdinalLength::Short, Tradition::Lutheran).unwrap(),
+            next_church_date(date, OrdinalLength::Long, Tradition::Lutheran).unwrap(),
+        )
+    }
+
+    const TOML_PLAN: &str = r#"
+        [[service]]
+        date = 2026-10-25
+        sermon_title = "Here I Stand"
+        preacher = "Pastor Ann"
+        extra_text = ["Confirmation Sunday"]
+        color = "red"
+
+        [[service]]
+        date = "2026-11-01"
+        sermon_title = "For All the Saints"
+
+        [[service]]
+        observance = "Christmas Eve"
+        extra_text = ["Candlelight Service"]
+
+        [[service]]
+        observance = "Reformation Sunday"
+        sermon_title = "Not Used, the date comes first"
+    "#;
+
+    #[test]
+    fn toml_plan() {
+        let plan = Plan::from_toml(TOML_PLAN).unwrap();
+
+        let (short, long) = church_dates("2026-10-25");
+        let service = plan.find(&short, &long).unwrap();
+        assert_eq!(service.sermon_title.as_deref(), Some("Here I Stand"));
+        assert_eq!(service.preacher.as_deref(), Some("Pastor Ann"));
+        assert_eq!(service.extra_text, vec!["Confirmation Sunday"]);
+        assert_eq!(service.color.as_deref(), Some("Red"));
+
+        let (short, long) = church_dates("2027-12-24");
+        let service = plan.find(&short, &long).unwrap();
+        assert_eq!(service.extra_text, vec!["Candlelight Service"]);
+
+        let (short, long) = church_dates("2026-10-26");
+        let service = plan.find(&short, &long).unwrap();
+        assert_eq!(service.sermon_title.as_deref(), Some("For All the Saints"));
+
+        let (short, long) = church_dates("2026-11-08");
+        assert!(plan.find(&short, &long).is_none());
+    }
+
+    #[test]
+    fn csv_plan() {
+        let plan = Plan::from_csv(
+            "date,observance,sermon_title,preacher,extra_text,color\n\
+             2026-10-25,,\"Here I Stand, Still\",Pastor Ann,Confirmation Sunday | Potluck,Red\n\
+             ,Christmas Eve,,,Candlelight Service,\n",
+        )
+        .unwrap();
+        assert_eq!(plan.services.len(), 2);
+        assert_eq!(
+            plan.services[0].sermon_title.as_deref(),
+            Some("Here I Stand, Still")
+        );
+        assert_eq!(
+            plan.services[0].extra_text,
+            vec!["Confirmation Sunday", "Potluck"]
+        );
+        assert_eq!(plan.services[1].date, None);
+        assert_eq!(
+            plan.services[1].observance.as_deref(),
+            Some("Christmas Eve")
+        );
+        assert_eq!(plan.services[1].color, None);
+    }
+
+    #[test]
+    fn bad_plans() {
+        assert!(Plan::from_toml("[[service]]\nsermon_title = \"Lost\"").is_err());
+        assert!(Plan::from_toml("[[service]]\ndate = 2026-10-25\ncolor = \"Pink\"").is_err());
+        assert!(Plan::from_toml("[[service]]\ndate = 2026-10-25\nspeaker = \"Ann\"").is_err());
+        assert!(Plan::from_toml("[[service]]\ndate = 2026-10-25T10:00:00").is_err());
+        assert!(Plan::from_csv("date,sermon_title\nOctober 25,Lost\n").is_err());
+    }
+
+    #[test]
+    fn plan_files() {
+        let directory = test_directory("plan");
+        assert!(matches!(load_plan(&directory.join("plan.toml")), Ok(None)));
+
+        // the same plan as TOML_PLAN
+        let yaml_path = directory.join("plan.yaml");
+        fs::write(
+            &yaml_path,
+            r#"
+service:
+  - date: 2026-10-25
+    sermon_title: Here I Stand
+    preacher: Pastor Ann
+    extra_text: [Confirmation Sunday]
+    color: red
+  - date: "2026-11-01"
+    sermon_title: For All the Saints
+  - observance: Christmas Eve
+    extra_text:
+      - Candlelight Service
+  - observance: Reformation Sunday
+    sermon_title: Not Used, the date comes first
+"#,
+        )
+        .unwrap();
+        let plan = load_plan(&yaml_path).unwrap().unwrap();
+        assert_eq!(plan.services, Plan::from_toml(TOML_PLAN).unwrap().services);
+
+        let yml_path = directory.join("plan.YML");
+        fs::write(&yml_path, "service:\n  - sermon_title: Lost\n").unwrap();
+        assert!(load_plan(&yml_path).is_err());
+
+        fs::remove_dir_all(&directory).unwrap();
+    }
+}
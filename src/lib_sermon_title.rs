@@ -0,0 +1,345 @@
+use std::collections::HashSet;
+use std::fmt;
+
+// Sermon titles are meant to be a few words chosen by the preacher, like
+// 'All Are Welcome' or '...And Peter', not names, dates or other information.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum SermonTitleProblem {
+    Date(String),
+    Preacher(String),
+    AllCaps,
+    TrailingPunctuation,
+    // the quotes are added when it's used
+    Quotes,
+    TooLong { length: usize, max_length: usize },
+}
+
+impl fmt::Display for SermonTitleProblem {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SermonTitleProblem::Date(date) => write!(f, "has a date in it: '{date}'"),
+            SermonTitleProblem::Preacher(preacher) => {
+                write!(f, "has the preacher's name in it: '{preacher}'")
+            }
+            SermonTitleProblem::AllCaps => write!(f, "is in all caps"),
+            SermonTitleProblem::TrailingPunctuation => write!(f, "ends with punctuation"),
+            SermonTitleProblem::Quotes => {
+                write!(f, "has quotes around it, they're added automatically")
+            }
+            SermonTitleProblem::TooLong { length, max_length } => write!(
+                f,
+                "is {length} characters long, try to keep it under {max_length}"
+            ),
+        }
+    }
+}
+
+const MONTHS: [&str; 12] = [
+    "january",
+    "february",
+    "march",
+    "april",
+    "may",
+    "june",
+    "july",
+    "august",
+    "september",
+    "october",
+    "november",
+    "december",
+];
+
+const DOUBLE_QUOTES: [char; 3] = ['"', '“', '”'];
+const SINGLE_QUOTES: [char; 3] = ['\'', '‘', '’'];
+
+// an ellipsis is allowed, like 'What Now...'
+const TRAILING_PUNCTUATION: [char; 6] = ['.', ',', ';', ':', '-', '–'];
+
+pub fn check_sermon_title(
+    title: &str,
+    preachers: &[String],
+    max_length: usize,
+) -> Vec<SermonTitleProblem> {
+    let mut problems = Vec::new();
+
+    if let Some(date) = find_date(title) {
+        problems.push(SermonTitleProblem::Date(date));
+    }
+
+    let lowercase_title = title.to_lowercase();
+    for preacher in preachers {
+        if !preacher.trim().is_empty() && lowercase_title.contains(&preacher.trim().to_lowercase())
+        {
+            problems.push(SermonTitleProblem::Preacher(preacher.trim().to_string()));
+        }
+    }
+
+    if is_all_caps(title) {
+        problems.push(SermonTitleProblem::AllCaps);
+    }
+    if has_trailing_punctuation(title.trim()) {
+        problems.push(SermonTitleProblem::TrailingPunctuation);
+    }
+    if has_quotes(title.trim()) {
+        problems.push(SermonTitleProblem::Quotes);
+    }
+
+    let length = title.trim().chars().count();
+    if length > max_length {
+        problems.push(SermonTitleProblem::TooLong { length, max_length });
+    }
+
+    problems
+}
+
+// fixes what can be fixed without asking, dates, names and long titles are
+// left for the person who wrote it
+pub fn corrected_sermon_title(title: &str) -> String {
+    let mut corrected = title.split_whitespace().collect::<Vec<_>>().join(" ");
+
+    while has_quotes(&corrected) {
+        corrected = corrected
+            .trim_matches(DOUBLE_QUOTES)
+            .trim_matches(|character| {
+                SINGLE_QUOTES.contains(&character) && wrapped_in_single_quotes(&corrected)
+            })
+            .trim()
+            .to_string();
+    }
+
+    while has_trailing_punctuation(&corrected) {
+        corrected = corrected
+            .trim_end_matches(TRAILING_PUNCTUATION)
+            .trim_end()
+            .to_string();
+    }
+
+    if is_all_caps(&corrected) {
+        corrected = title_case(&corrected);
+    }
+
+    corrected
+}
+
+pub fn quoted_sermon_title(title: &str, typographic_quotes: bool) -> String {
+    if typographic_quotes {
+        format!("“{title}”")
+    } else {
+        format!("\"{title}\"")
+    }
+}
+
+fn find_date(title: &str) -> Option<String> {
+    let words: Vec<&str> = title.split_whitespace().collect();
+    for (position, word) in words.iter().enumerate() {
+        let word = word.trim_matches(|character: char| !character.is_alphanumeric());
+
+        // 10/25, 10-25-26, 2026-10 or 25.10.26, but not Matthew 5-7, John 3.16
+        // or Genesis 1.1-2.4, a date uses the same separator throughout
+        let separators = word
+            .chars()
+            .filter(|character| ['/', '-', '.'].contains(character))
+            .collect::<HashSet<_>>();
+        let number_groups = word
+            .split(['/', '-', '.'])
+            .filter(|group| !group.is_empty())
+            .collect::<Vec<_>>();
+        let looks_like_a_date = separators.len() == 1
+            && (number_groups.len() >= 3
+                || number_groups.iter().any(|group| group.len() == 4)
+                || word.contains('/'))
+            // days, months and years, not verse 100
+            && number_groups
+                .iter()
+                .all(|group| matches!(group.len(), 1 | 2 | 4));
+        if number_groups.len() >= 2
+            && looks_like_a_date
+            && number_groups
+                .iter()
+                .all(|group| group.chars().all(|character| character.is_ascii_digit()))
+        {
+            return Some(word.to_string());
+        }
+
+        // October 25th, Oct. 25 or 25 October
+        if is_month(word) {
+            let is_day = |word: &str| {
+                let word = word.trim_matches(|character: char| !character.is_alphanumeric());
+                let day = ["st", "nd", "rd", "th"]
+                    .iter()
+                    .find_map(|suffix| word.strip_suffix(suffix))
+                    .unwrap_or(word);
+                day.parse::<u32>().is_ok_and(|day| (1..=31).contains(&day))
+            };
+            if let Some(next_word) = words.get(position + 1).filter(|word| is_day(word)) {
+                return Some(format!("{} {next_word}", words[position]));
+            }
+            if let Some(previous_word) = position
+                .checked_sub(1)
+                .and_then(|position| words.get(position))
+                .filter(|word| is_day(word))
+            {
+                return Some(format!("{previous_word} {}", words[position]));
+            }
+        }
+    }
+    None
+}
+
+fn is_month(word: &str) -> bool {
+    let word = word.to_lowercase();
+    MONTHS
+        .iter()
+        .any(|month| *month == word || (word.len() >= 3 && month.starts_with(&word)))
+}
+
+fn is_all_caps(title: &str) -> bool {
+    let letters = title.chars().filter(|character| character.is_alphabetic());
+    letters.clone().count() >= 4 && letters.clone().all(char::is_uppercase)
+}
+
+fn has_trailing_punctuation(title: &str) -> bool {
+    !title.ends_with("...") && title.ends_with(TRAILING_PUNCTUATION)
+}
+
+fn wrapped_in_single_quotes(title: &str) -> bool {
+    title.chars().count() >= 2 && title.starts_with(SINGLE_QUOTES) && title.ends_with(SINGLE_QUOTES)
+}
+
+fn has_quotes(title: &str) -> bool {
+    title.starts_with(DOUBLE_QUOTES)
+        || title.ends_with(DOUBLE_QUOTES)
+        || wrapped_in_single_quotes(title)
+}
+
+// every word is capitalized, like 'All Are Welcome'
+fn title_case(title: &str) -> String {
+    title
+        .split(' ')
+        .map(|word| {
+            let mut characters = word.chars();
+            let mut capitalized = String::new();
+            // skip over leading punctuation like '...And'
+            for character in characters.by_ref() {
+                capitalized.push(character);
+                if character.is_alphabetic() {
+                    break;
+                }
+            }
+            capitalized + &characters.as_str().to_lowercase()
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn problems(title: &str) -> Vec<SermonTitleProblem> {
+        check_sermon_title(title, &["Pastor Ann".to_string()], 50)
+    }
+
+    #[test]
+    fn good_titles() {
+        assert!(problems("All Are Welcome").is_empty());
+        assert!(problems("...And Peter").is_empty());
+        assert!(problems("Who Is My Neighbor?").is_empty());
+        assert!(problems("Peter's Denial").is_empty());
+        assert!(problems("May You Be Blessed").is_empty());
+        assert!(problems("Wait for It...").is_empty());
+    }
+
+    #[test]
+    fn dates() {
+        for (title, date) in [
+            ("All Are Welcome 10/25", "10/25"),
+            ("10-25-26 All Are Welcome", "10-25-26"),
+            ("Sermon for October 25th", "October 25th"),
+            ("Sermon, Oct. 25", "Oct. 25"),
+            ("25 October Sermon", "25 October"),
+            ("Sermon 25.10.26", "25.10.26"),
+            ("Reformation 2026-10", "2026-10"),
+        ] {
+            assert_eq!(
+                problems(title),
+                vec![SermonTitleProblem::Date(date.to_string())],
+                "{title}"
+            );
+        }
+
+        // scripture references aren't dates
+        for title in [
+            "Matthew 5-7",
+            "John 3.16",
+            "John 3:16-17",
+            "The Sermon on the Mount (Matthew 5-7)",
+            "Romans 8.28-30",
+            "Genesis 1.1-2.4",
+        ] {
+            assert!(problems(title).is_empty(), "{title}");
+        }
+    }
+
+    #[test]
+    fn other_problems() {
+        assert_eq!(
+            problems("All Are Welcome - pastor ann"),
+            vec![SermonTitleProblem::Preacher("Pastor Ann".to_string())]
+        );
+        assert_eq!(
+            problems("ALL ARE WELCOME."),
+            vec![
+                SermonTitleProblem::AllCaps,
+                SermonTitleProblem::TrailingPunctuation
+            ]
+        );
+        assert_eq!(
+            problems("\"All Are Welcome\""),
+            vec![SermonTitleProblem::Quotes]
+        );
+        assert_eq!(
+            problems(&"Welcome ".repeat(8)),
+            vec![SermonTitleProblem::TooLong {
+                length: 63,
+                max_length: 50
+            }]
+        );
+    }
+
+    #[test]
+    fn corrections() {
+        assert_eq!(
+            corrected_sermon_title("  \"ALL ARE  WELCOME.\" "),
+            "All Are Welcome"
+        );
+        assert_eq!(
+            corrected_sermon_title("“All Are Welcome”"),
+            "All Are Welcome"
+        );
+        assert_eq!(
+            corrected_sermon_title("'All Are Welcome'"),
+            "All Are Welcome"
+        );
+        assert_eq!(corrected_sermon_title("...AND PETER"), "...And Peter");
+        assert_eq!(corrected_sermon_title("Peters'"), "Peters'");
+        assert_eq!(corrected_sermon_title("Wait for It..."), "Wait for It...");
+        // corrections don't need correcting again
+        for title in ["\"ALL ARE WELCOME.\"", "All Are Welcome - Pastor Ann", "'"] {
+            let corrected = corrected_sermon_title(title);
+            assert_eq!(corrected_sermon_title(&corrected), corrected);
+        }
+    }
+
+    #[test]
+    fn quotes() {
+        assert_eq!(
+            quoted_sermon_title("All Are Welcome", false),
+            "\"All Are Welcome\""
+        );
+        assert_eq!(
+            quoted_sermon_title("All Are Welcome", true),
+            "“All Are Welcome”"
+        );
+    }
+}
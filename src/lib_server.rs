@@ -0,0 +1,320 @@
+use serde::Serialize;
+use std::fs::{self, File};
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Sender};
+use std::sync::Mutex;
+use std::thread::{self, Scope};
+use std::time::{Duration, SystemTime};
+use tiny_http::{Header, Request, Response, Server};
+use tungstenite::handshake::derive_accept_key;
+use tungstenite::protocol::Role;
+use tungstenite::{Message, WebSocket};
+
+use church_date_updater::lib_error::Error;
+
+use crate::lib_config::{Config, OverlayLine};
+use crate::lib_history::read_history;
+use crate::lib_lower_third::LowerThirdData;
+use crate::lib_overlay::overlay_path;
+
+// Publishing is a separate run, so the files it writes are watched. Every
+// overlay that's open is sent the change over a WebSocket, instead of it
+// reading the files again every 5 seconds.
+const CHECK_INTERVAL: Duration = Duration::from_millis(250);
+
+// what the overlay shows, `/state`
+#[derive(Serialize, PartialEq, Debug)]
+pub struct State {
+    pub lines: Vec<OverlayLine>,
+    // the color of the last run that changed it
+    pub color: Option<String>,
+}
+
+// sent to the overlays, `{"type": "state", "lines": [...], "color": "Red"}`
+#[derive(Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum Update<'a> {
+    State(&'a State),
+    // the overlay itself changed, like its color, so it's loaded again
+    Reload,
+}
+
+pub fn serve(config: &Config, port: u16) -> Result<(), Error> {
+    let server = Server::http(("127.0.0.1", port))
+        .map_err(|err| Error::Server(format!("port {port}: {err}")))?;
+    println!("Add 'http://localhost:{port}/' to OBS as a browser source.");
+    println!("The color image is 'http://localhost:{port}/color.png'. Press Ctrl+C to stop.");
+    run(&server, config);
+    Ok(())
+}
+
+// until the program is closed, or the server is unblocked in the tests
+fn run(server: &Server, config: &Config) {
+    let sockets = Mutex::new(Vec::new());
+    let stopped = AtomicBool::new(false);
+    thread::scope(|scope| {
+        scope.spawn(|| watch(config, &sockets, &stopped));
+        for request in server.incoming_requests() {
+            respond(scope, request, config, &sockets);
+        }
+        // the threads of the open overlays stop when there's nothing left to
+        // send them
+        stopped.store(true, Ordering::Relaxed);
+        sockets.lock().unwrap().clear();
+    });
+}
+
+fn respond<'scope>(
+    scope: &'scope Scope<'scope, '_>,
+    request: Request,
+    config: &'scope Config,
+    sockets: &'scope Mutex<Vec<Sender<String>>>,
+) {
+    let paths = &config.paths;
+    let url = request
+        .url()
+        .split('?')
+        .next()
+        .unwrap_or_default()
+        .to_string();
+    // a closed browser source isn't a problem
+    let _ = match url.as_str() {
+        "/" | "/church_date.html" => {
+            respond_with_file(request, &overlay_path(paths), "text/html; charset=utf-8")
+        }
+        "/lower_data.json" => {
+            respond_with_file(request, &paths.lower_third_json, "application/json")
+        }
+        "/lower_data.txt" => {
+            respond_with_file(request, &paths.lower_third, "text/plain; charset=utf-8")
+        }
+        "/color.png" => respond_with_file(request, &paths.color_image, "image/png"),
+        "/state" => {
+            let state = serde_json::to_string(&read_state(config)).unwrap_or_default();
+            request.respond(
+                Response::from_string(state)
+                    .with_header(header("Content-Type", "application/json")),
+            )
+        }
+        "/events" => {
+            accept_socket(scope, request, config, sockets);
+            Ok(())
+        }
+        _ => request.respond(Response::from_string("Not found").with_status_code(404)),
+    };
+}
+
+fn header(field: &str, value: &str) -> Header {
+    Header::from_bytes(field.as_bytes(), value.as_bytes()).expect("the headers are valid")
+}
+
+fn respond_with_file(request: Request, path: &Path, content_type: &str) -> std::io::Result<()> {
+    match File::open(path) {
+        Ok(file) => request.respond(
+            Response::from_file(file)
+                .with_header(header("Content-Type", content_type))
+                .with_header(header("Cache-Control", "no-store")),
+        ),
+        Err(_) => request.respond(
+            Response::from_string(format!("'{}' doesn't exist yet", path.display()))
+                .with_status_code(404),
+        ),
+    }
+}
+
+// each overlay gets its own thread that sends it the updates
+fn accept_socket<'scope>(
+    scope: &'scope Scope<'scope, '_>,
+    request: Request,
+    config: &Config,
+    sockets: &'scope Mutex<Vec<Sender<String>>>,
+) {
+    let key = request
+        .headers()
+        .iter()
+        .find(|header| header.field.equiv("Sec-WebSocket-Key"))
+        .map(|header| derive_accept_key(header.value.as_bytes()));
+    let Some(key) = key else {
+        let _ = request.respond(Response::from_string("Only for WebSockets").with_status_code(400));
+        return;
+    };
+    let stream = request.upgrade(
+        "websocket",
+        Response::empty(101).with_header(header("Sec-WebSocket-Accept", &key)),
+    );
+    let mut socket = WebSocket::from_raw_socket(stream, Role::Server, None);
+
+    let (sender, receiver) = mpsc::channel();
+    // what's shown now, before the first change
+    let _ = sender.send(update(&Update::State(&read_state(config))));
+    sockets.lock().unwrap().push(sender);
+    scope.spawn(move || {
+        for message in receiver {
+            if socket.send(Message::text(message)).is_err() {
+                break;
+            }
+        }
+    });
+}
+
+fn update(update: &Update) -> String {
+    serde_json::to_string(update).unwrap_or_default()
+}
+
+fn watch(config: &Config, sockets: &Mutex<Vec<Sender<String>>>, stopped: &AtomicBool) {
+    let paths = &config.paths;
+    let modified = |path: &Path| {
+        fs::metadata(path)
+            .and_then(|metadata| metadata.modified())
+            .ok()
+    };
+    let state_files = [
+        &paths.lower_third_json,
+        &paths.lower_third,
+        &paths.color_image,
+        &paths.history,
+    ];
+    let state_modified =
+        || -> Vec<Option<SystemTime>> { state_files.iter().map(|path| modified(path)).collect() };
+    let overlay = overlay_path(paths);
+
+    let mut last_state = state_modified();
+    let mut last_overlay = modified(&overlay);
+    while !stopped.load(Ordering::Relaxed) {
+        thread::sleep(CHECK_INTERVAL);
+        let mut message = None;
+        if modified(&overlay) != last_overlay {
+            last_overlay = modified(&overlay);
+            message = Some(update(&Update::Reload));
+        } else if state_modified() != last_state {
+            message = Some(update(&Update::State(&read_state(config))));
+        }
+        // a reload reads the state again too
+        last_state = state_modified();
+
+        if let Some(message) = message {
+            let mut sockets = sockets.lock().unwrap();
+            // the ones that can't be sent to were closed
+            sockets.retain(|socket| socket.send(message.clone()).is_ok());
+            println!("Sent the change to the open overlays ({}).", sockets.len());
+        }
+    }
+}
+
+// `lower_data.json`, or `lower_data.txt` when there isn't one, like the
+// overlay itself
+pub fn read_state(config: &Config) -> State {
+    let paths = &config.paths;
+    let lines = fs::read_to_string(&paths.lower_third_json)
+        .ok()
+        .and_then(|json| serde_json::from_str::<LowerThirdData>(&json).ok())
+        .map(|data| data.lines)
+        .unwrap_or_else(|| {
+            let text = fs::read_to_string(&paths.lower_third).unwrap_or_default();
+            text.lines()
+                .map(|text| config.lower_third.overlay_line(text))
+                .collect()
+        });
+    let color = read_history(&paths.history)
+        .ok()
+        .and_then(|entries| entries.into_iter().rev().find_map(|entry| entry.color));
+    State { lines, color }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lib_config::Paths;
+    use crate::lib_lower_third::write_lower_data;
+    use crate::lib_test_directory::test_directory;
+    use std::io::{Read, Write};
+    use std::net::TcpStream;
+    use std::sync::Arc;
+
+    fn lines(state: &serde_json::Value) -> Vec<&str> {
+        state["lines"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|line| line["text"].as_str().unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn sends_changes() {
+        let directory = test_directory("serve");
+        let config = Config {
+            paths: Paths::default().resolved(&directory),
+            ..Config::default()
+        };
+        fs::write(
+            &config.paths.lower_third,
+            "October 25th, Reformation Sunday\n",
+        )
+        .unwrap();
+        fs::write(overlay_path(&config.paths), "<p>Overlay</p>").unwrap();
+
+        let server = Arc::new(Server::http("127.0.0.1:0").unwrap());
+        let address = server.server_addr().to_ip().unwrap();
+        let paths = Paths::default().resolved(&directory);
+        let running = {
+            let server = Arc::clone(&server);
+            thread::spawn(move || run(&server, &config))
+        };
+
+        // `lower_data.txt` is used until there's a `lower_data.json`
+        let mut stream = TcpStream::connect(address).unwrap();
+        stream
+            .write_all(b"GET /state HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n")
+            .unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        let (_, body) = response.split_once("\r\n\r\n").unwrap();
+        let state: serde_json::Value = serde_json::from_str(body).unwrap();
+        assert_eq!(lines(&state), ["October 25th, Reformation Sunday"]);
+        assert_eq!(state["color"], serde_json::Value::Null);
+
+        // a change that's never sent fails the test instead of hanging it
+        let stream = TcpStream::connect(address).unwrap();
+        stream
+            .set_read_timeout(Some(Duration::from_secs(5)))
+            .unwrap();
+        let mut socket = tungstenite::client(format!("ws://{address}/events"), stream)
+            .unwrap()
+            .0;
+        let mut receive = || -> serde_json::Value {
+            let message = socket.read().unwrap();
+            serde_json::from_str(message.to_text().unwrap()).unwrap()
+        };
+        let update = receive();
+        assert_eq!(update["type"], "state");
+        assert_eq!(lines(&update), ["October 25th, Reformation Sunday"]);
+
+        write_lower_data(
+            &paths.lower_third,
+            &paths.lower_third_json,
+            &LowerThirdData::new(&["Welcome!".to_string()], &Default::default()),
+        )
+        .unwrap();
+        let update = receive();
+        assert_eq!(update["type"], "state");
+        assert_eq!(lines(&update), ["Welcome!"]);
+        assert_eq!(update["lines"][0]["duration_seconds"], 60);
+        fs::write(overlay_path(&paths), "<p>Purple overlay</p>").unwrap();
+
+        // the text file can be noticed before the JSON one
+        let reloaded = (0..10).any(|_| {
+            let reload = receive()["type"] == "reload";
+            if !reload {
+                fs::write(overlay_path(&paths), "<p>Purple overlay</p>").unwrap();
+            }
+            reload
+        });
+        assert!(reloaded);
+
+        server.unblock();
+        running.join().unwrap();
+        fs::remove_dir_all(&directory).unwrap();
+    }
+}
@@ -0,0 +1,348 @@
+use chrono::format::{Item, StrftimeItems};
+use chrono::NaiveDate;
+use std::fmt::{self, Write};
+use std::str::FromStr;
+
+// A title or lower third line with placeholders:
+// - `{name}` is replaced with the value of `name`
+// - `{date:%m-%d-%y}` formats a date with a chrono format
+// - `{#name}...{/name}` is only used when `name` isn't empty
+// - `{^name}...{/name}` is only used when `name` is empty
+// - `{{` and `}}` are a single `{` and `}`
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Template {
+    text: String,
+    parts: Vec<Part>,
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+enum Part {
+    Text(String),
+    Value {
+        name: String,
+        format: Option<String>,
+    },
+    Section {
+        name: String,
+        // shown when the value is empty instead
+        inverted: bool,
+        parts: Vec<Part>,
+    },
+}
+
+pub enum TemplateValue {
+    Text(String),
+    // a date and the format used when the template doesn't give one
+    Date(NaiveDate, String),
+}
+
+impl TemplateValue {
+    fn is_empty(&self) -> bool {
+        match self {
+            TemplateValue::Text(text) => text.is_empty(),
+            TemplateValue::Date(..) => false,
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum TemplateError {
+    UnclosedBrace,
+    UnopenedBrace,
+    EmptyName,
+    UnclosedSection(String),
+    UnopenedSection(String),
+    BadDateFormat(String),
+    UnknownName(String),
+    NotADate(String),
+}
+
+impl fmt::Display for TemplateError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TemplateError::UnclosedBrace => {
+                write!(f, "a '{{' is never closed, use '{{{{' for a brace")
+            }
+            TemplateError::UnopenedBrace => {
+                write!(f, "a '}}' is never opened, use '}}}}' for a brace")
+            }
+            TemplateError::EmptyName => write!(f, "'{{}}' needs a name"),
+            TemplateError::UnclosedSection(name) => {
+                write!(f, "'{{#{name}}}' needs a '{{/{name}}}'")
+            }
+            TemplateError::UnopenedSection(name) => {
+                write!(f, "'{{/{name}}}' doesn't close a section")
+            }
+            TemplateError::BadDateFormat(format) => {
+                write!(f, "'{format}' isn't a date format")
+            }
+            TemplateError::UnknownName(name) => write!(f, "'{{{name}}}' isn't a placeholder"),
+            TemplateError::NotADate(name) => {
+                write!(f, "'{{{name}}}' isn't a date, so it can't have a format")
+            }
+        }
+    }
+}
+
+impl std::error::Error for TemplateError {}
+
+impl Template {
+    // `values` returns `None` for names that aren't placeholders
+    pub fn render<F>(&self, values: F) -> Result<String, TemplateError>
+    where
+        F: Fn(&str) -> Option<TemplateValue>,
+    {
+        let mut rendered = String::new();
+        render_parts(&self.parts, &values, &mut rendered)?;
+        Ok(rendered)
+    }
+}
+
+fn render_parts<F>(parts: &[Part], values: &F, rendered: &mut String) -> Result<(), TemplateError>
+where
+    F: Fn(&str) -> Option<TemplateValue>,
+{
+    for part in parts {
+        match part {
+            Part::Text(text) => rendered.push_str(text),
+            Part::Value { name, format } => match (lookup(values, name)?, format) {
+                (TemplateValue::Text(text), None) => rendered.push_str(&text),
+                // a value that was left out, like a date that didn't fit
+                (TemplateValue::Text(text), Some(_)) if text.is_empty() => {}
+                (TemplateValue::Text(_), Some(_)) => {
+                    return Err(TemplateError::NotADate(name.clone()));
+                }
+                (TemplateValue::Date(date, default_format), format) => {
+                    let format = format.as_ref().unwrap_or(&default_format);
+                    rendered.push_str(&formatted_date(date, format)?);
+                }
+            },
+            Part::Section {
+                name,
+                inverted,
+                parts,
+            } => {
+                if lookup(values, name)?.is_empty() == *inverted {
+                    render_parts(parts, values, rendered)?;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+fn lookup<F>(values: &F, name: &str) -> Result<TemplateValue, TemplateError>
+where
+    F: Fn(&str) -> Option<TemplateValue>,
+{
+    values(name).ok_or_else(|| TemplateError::UnknownName(name.to_string()))
+}
+
+impl FromStr for Template {
+    type Err = TemplateError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        // the sections being read, the outermost is the whole template
+        let mut open_sections: Vec<(String, bool, Vec<Part>)> =
+            vec![(String::new(), false, Vec::new())];
+        let mut literal = String::new();
+        let mut characters = text.chars().peekable();
+
+        while let Some(character) = characters.next() {
+            match character {
+                '{' if characters.peek() == Some(&'{') => {
+                    characters.next();
+                    literal.push('{');
+                }
+                '}' if characters.peek() == Some(&'}') => {
+                    characters.next();
+                    literal.push('}');
+                }
+                '}' => return Err(TemplateError::UnopenedBrace),
+                '{' => {
+                    let mut placeholder = String::new();
+                    loop {
+                        match characters.next() {
+                            Some('}') => break,
+                            Some(character) => placeholder.push(character),
+                            None => return Err(TemplateError::UnclosedBrace),
+                        }
+                    }
+
+                    let parts = &mut open_sections
+                        .last_mut()
+                        .expect("The whole template should always be open.")
+                        .2;
+                    if !literal.is_empty() {
+                        parts.push(Part::Text(std::mem::take(&mut literal)));
+                    }
+
+                    if let Some(name) = placeholder.strip_prefix('#') {
+                        open_sections.push((checked_name(name)?, false, Vec::new()));
+                    } else if let Some(name) = placeholder.strip_prefix('^') {
+                        open_sections.push((checked_name(name)?, true, Vec::new()));
+                    } else if let Some(name) = placeholder.strip_prefix('/') {
+                        let name = checked_name(name)?;
+                        if open_sections.len() == 1 || open_sections.last().unwrap().0 != name {
+                            return Err(TemplateError::UnopenedSection(name));
+                        }
+                        let (name, inverted, parts) = open_sections.pop().unwrap();
+                        open_sections.last_mut().unwrap().2.push(Part::Section {
+                            name,
+                            inverted,
+                            parts,
+                        });
+                    } else {
+                        let (name, format) = match placeholder.split_once(':') {
+                            Some((name, format)) => (name, Some(checked_format(format)?)),
+                            None => (placeholder.as_str(), None),
+                        };
+                        parts.push(Part::Value {
+                            name: checked_name(name)?,
+                            format,
+                        });
+                    }
+                }
+                character => literal.push(character),
+            }
+        }
+
+        if open_sections.len() > 1 {
+            return Err(TemplateError::UnclosedSection(
+                open_sections.pop().unwrap().0,
+            ));
+        }
+        let mut parts = open_sections.pop().unwrap().2;
+        if !literal.is_empty() {
+            parts.push(Part::Text(literal));
+        }
+
+        Ok(Template {
+            text: text.to_string(),
+            parts,
+        })
+    }
+}
+
+fn checked_name(name: &str) -> Result<String, TemplateError> {
+    let name = name.trim();
+    if name.is_empty() {
+        Err(TemplateError::EmptyName)
+    } else {
+        Ok(name.to_string())
+    }
+}
+
+fn checked_format(format: &str) -> Result<String, TemplateError> {
+    if StrftimeItems::new(format).any(|item| item == Item::Error) {
+        return Err(TemplateError::BadDateFormat(format.to_string()));
+    }
+    // the format is fine, but might need a time or a time zone a date doesn't have
+    let sample_date = NaiveDate::from_ymd_opt(2026, 10, 25).unwrap();
+    formatted_date(sample_date, format)?;
+    Ok(format.to_string())
+}
+
+// `date.format(format).to_string()` panics for formats like `%H`
+fn formatted_date(date: NaiveDate, format: &str) -> Result<String, TemplateError> {
+    let mut formatted = String::new();
+    write!(formatted, "{}", date.format(format))
+        .map_err(|_| TemplateError::BadDateFormat(format.to_string()))?;
+    Ok(formatted)
+}
+
+impl fmt::Display for Template {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn values(name: &str) -> Option<TemplateValue> {
+        let text = |text: &str| Some(TemplateValue::Text(text.to_string()));
+        match name {
+            "sermon_title" => text("All Are Welcome"),
+            "extra_text" => text(""),
+            "observance.long" => text("Twenty-Second Sunday after Pentecost"),
+            "date" => Some(TemplateValue::Date(
+                NaiveDate::from_ymd_opt(2026, 10, 25).unwrap(),
+                "%m-%d-%y".to_string(),
+            )),
+            _ => None,
+        }
+    }
+
+    fn render(template: &str) -> Result<String, TemplateError> {
+        template.parse::<Template>()?.render(values)
+    }
+
+    #[test]
+    fn placeholders() {
+        assert_eq!(
+            render("\"{sermon_title}\" - {date} - {observance.long}").unwrap(),
+            "\"All Are Welcome\" - 10-25-26 - Twenty-Second Sunday after Pentecost"
+        );
+        assert_eq!(render("{date:%B %-d, %Y}").unwrap(), "October 25, 2026");
+        assert_eq!(render("{{literal}}").unwrap(), "{literal}");
+        // left out values don't have a format
+        assert_eq!(render("[{extra_text:%Y}]").unwrap(), "[]");
+        assert_eq!(render("").unwrap(), "");
+    }
+
+    #[test]
+    fn sections() {
+        assert_eq!(
+            render("{#sermon_title}\"{sermon_title}\" - {/sermon_title}{date}").unwrap(),
+            "\"All Are Welcome\" - 10-25-26"
+        );
+        assert_eq!(
+            render("{#extra_text}{extra_text} - {/extra_text}{date}").unwrap(),
+            "10-25-26"
+        );
+        assert_eq!(
+            render("{^extra_text}no extra text{/extra_text}").unwrap(),
+            "no extra text"
+        );
+        assert_eq!(
+            render("{#sermon_title}{^extra_text}nested{/extra_text}{/sermon_title}").unwrap(),
+            "nested"
+        );
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(render("{date"), Err(TemplateError::UnclosedBrace));
+        assert_eq!(render("date}"), Err(TemplateError::UnopenedBrace));
+        assert_eq!(render("{}"), Err(TemplateError::EmptyName));
+        assert_eq!(
+            render("{#sermon_title}"),
+            Err(TemplateError::UnclosedSection("sermon_title".to_string()))
+        );
+        assert_eq!(
+            render("{#sermon_title}{/date}"),
+            Err(TemplateError::UnopenedSection("date".to_string()))
+        );
+        assert_eq!(
+            render("{date:%Q}"),
+            Err(TemplateError::BadDateFormat("%Q".to_string()))
+        );
+        // a date doesn't have a time or a time zone
+        for format in ["%H:%M", "%H", "%M", "%Z"] {
+            assert_eq!(
+                render(&format!("{{date:{format}}} - {{sermon_title}}")),
+                Err(TemplateError::BadDateFormat(format.to_string()))
+            );
+        }
+        assert_eq!(
+            render("{preacher}"),
+            Err(TemplateError::UnknownName("preacher".to_string()))
+        );
+        assert_eq!(
+            render("{sermon_title:%Y}"),
+            Err(TemplateError::NotADate("sermon_title".to_string()))
+        );
+    }
+}
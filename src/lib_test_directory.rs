@@ -0,0 +1,12 @@
+use std::fs;
+use std::path::PathBuf;
+
+// an empty folder for a test to write in, each test gives it its own name so
+// they can run at the same time
+pub fn test_directory(name: &str) -> PathBuf {
+    let directory =
+        std::env::temp_dir().join(format!("church-date-updater-{name}-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&directory);
+    fs::create_dir_all(&directory).unwrap();
+    directory
+}
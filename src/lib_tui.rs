@@ -0,0 +1,686 @@
+use chrono::{Duration, NaiveDate};
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Color as TerminalColor, Style, Stylize};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, List, ListItem, ListState, Paragraph};
+use ratatui::{DefaultTerminal, Frame};
+use std::io;
+
+use church_date_updater::lib_church_date::next_church_date;
+use church_date_updater::lib_church_date::ChurchDateResult;
+use church_date_updater::lib_church_date::OrdinalLength::{Long, Short};
+use church_date_updater::lib_error::Error;
+use church_date_updater::lib_sermon_title::check_sermon_title;
+
+use crate::lib_cli_args::COLOR_NAMES;
+use crate::lib_config::{Config, TitleValues, YOUTUBE_TITLE_LIMIT};
+use crate::lib_lower_third::{LineKind, LowerThird, LowerThirdData};
+use crate::lib_plan::Plan;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum EditTarget {
+    Line(usize),
+    NewExtraText,
+    NewSermonTitle,
+    YoutubeTitle,
+}
+
+struct Edit {
+    target: EditTarget,
+    text: String,
+}
+
+#[derive(PartialEq, Debug)]
+pub enum Action {
+    Continue,
+    Publish,
+    Quit,
+}
+
+// everything the steps need once Publish is pressed
+pub struct Choices {
+    pub target_date: NaiveDate,
+    pub lower_third: LowerThirdData,
+    pub color: &'static str,
+    pub youtube_title: String,
+}
+
+pub struct App<'a> {
+    config: &'a Config,
+    plan: Option<&'a Plan>,
+    recurring_lines: &'a [String],
+    target_date: NaiveDate,
+    church_date: ChurchDateResult,
+    church_date_long: ChurchDateResult,
+    // an index into COLOR_NAMES
+    color: usize,
+    lower_third: LowerThird,
+    selected: usize,
+    preacher: String,
+    // typed instead of the generated one
+    youtube_title: Option<String>,
+    edit: Option<Edit>,
+    // shown until the next key
+    message: Option<String>,
+}
+
+impl<'a> App<'a> {
+    // starts with the church date, the sermon title, extra text and color from
+    // the plan, and the lines added every week
+    pub fn for_date(
+        config: &'a Config,
+        plan: Option<&'a Plan>,
+        recurring_lines: &'a [String],
+        target_date: NaiveDate,
+    ) -> Result<App<'a>, Error> {
+        let church_date = next_church_date(target_date, Short, config.tradition)?;
+        let church_date_long = next_church_date(target_date, Long, config.tradition)?;
+        let planned_service = plan
+            .and_then(|plan| plan.find(&church_date, &church_date_long))
+            .cloned()
+            .unwrap_or_default();
+
+        let suggested_color = color_position(&church_date.color.to_string()).unwrap_or(0);
+        let color = planned_service
+            .color
+            .as_deref()
+            .and_then(color_position)
+            .unwrap_or(suggested_color);
+
+        let mut app = App {
+            config,
+            plan,
+            recurring_lines,
+            target_date,
+            church_date,
+            church_date_long,
+            color,
+            lower_third: LowerThird::default(),
+            selected: 0,
+            preacher: planned_service.preacher.unwrap_or_default(),
+            youtube_title: None,
+            edit: None,
+            message: None,
+        };
+
+        let church_date_line = app.church_date_line();
+        app.lower_third.push(LineKind::ChurchDate, church_date_line);
+        if let Some(sermon_title) = planned_service.sermon_title {
+            app.lower_third.push(LineKind::SermonTitle, sermon_title);
+        }
+        for extra_text in planned_service.extra_text {
+            app.lower_third.push(LineKind::ExtraText, extra_text);
+        }
+        app.lower_third.add_recurring(recurring_lines);
+        Ok(app)
+    }
+
+    fn title_values<'b>(
+        &'b self,
+        church_date: &'b str,
+        sermon_title: &'b str,
+        extra_texts: &'b [String],
+    ) -> TitleValues<'b> {
+        TitleValues {
+            // the service's date, when preparing ahead of it
+            date: self.church_date.date,
+            locale: self.config.locale,
+            church_date,
+            observance_short: &self.church_date.text,
+            observance_long: &self.church_date_long.text,
+            season: self.church_date.season,
+            color: self.church_date.color,
+            sermon_title,
+            typographic_quotes: self.config.sermon_titles.typographic_quotes,
+            preacher: &self.preacher,
+            extra_texts,
+        }
+    }
+
+    // the church date line as the settings make it
+    fn church_date_line(&self) -> String {
+        self.title_values(&self.church_date.text, "", &[])
+            .fill(&self.config.titles.lower_third_church_date)
+    }
+
+    fn sermon_title(&self) -> &str {
+        self.lower_third
+            .find(LineKind::SermonTitle)
+            .unwrap_or_default()
+    }
+
+    // the long church date, unless the one in the lower third was changed by hand
+    fn youtube_church_date(&self) -> &str {
+        match self.lower_third.find(LineKind::ChurchDate) {
+            Some(text) if text != self.church_date_line() => text,
+            _ => &self.church_date_long.text,
+        }
+    }
+
+    pub fn lower_third(&self) -> Vec<String> {
+        let extra_texts = self.lower_third.extra_texts();
+        self.lower_third.texts(
+            &self.title_values(self.youtube_church_date(), "", &extra_texts),
+            &self.config.titles.lower_third_sermon_title,
+        )
+    }
+
+    pub fn lower_third_data(&self) -> LowerThirdData {
+        let extra_texts = self.lower_third.extra_texts();
+        self.lower_third.data(
+            &self.title_values(self.youtube_church_date(), "", &extra_texts),
+            &self.config.titles.lower_third_sermon_title,
+            &self.config.lower_third,
+        )
+    }
+
+    // made from the lines as they are now, shortened when it's too long
+    pub fn youtube_title(&self) -> String {
+        if let Some(youtube_title) = &self.youtube_title {
+            return youtube_title.clone();
+        }
+        let extra_texts = self.lower_third.extra_texts();
+        let values = self.title_values(
+            self.youtube_church_date(),
+            self.sermon_title(),
+            &extra_texts,
+        );
+        let youtube_title = values.fill(&self.config.titles.youtube);
+        if youtube_title.chars().count() <= YOUTUBE_TITLE_LIMIT {
+            youtube_title
+        } else {
+            values.shortened(&self.config.titles.youtube, YOUTUBE_TITLE_LIMIT)
+        }
+    }
+
+    pub fn choices(&self) -> Choices {
+        Choices {
+            target_date: self.target_date,
+            lower_third: self.lower_third_data(),
+            color: COLOR_NAMES[self.color],
+            youtube_title: self.youtube_title(),
+        }
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) -> Action {
+        self.message = None;
+        if self.edit.is_some() {
+            self.handle_edit_key(key);
+            return Action::Continue;
+        }
+
+        let shift = key.modifiers.contains(KeyModifiers::SHIFT);
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => return Action::Quit,
+            KeyCode::Char('p') => return Action::Publish,
+            KeyCode::Up if shift => self.move_line(false),
+            KeyCode::Down if shift => self.move_line(true),
+            KeyCode::Char('K') => self.move_line(false),
+            KeyCode::Char('J') => self.move_line(true),
+            KeyCode::Up | KeyCode::Char('k') => self.selected = self.selected.saturating_sub(1),
+            KeyCode::Down | KeyCode::Char('j')
+                if self.selected + 1 < self.lower_third.lines.len() =>
+            {
+                self.selected += 1;
+            }
+            KeyCode::Enter | KeyCode::Char('e') => {
+                if let Some(line) = self.lower_third.lines.get(self.selected) {
+                    self.start_edit(EditTarget::Line(self.selected), line.text.clone());
+                }
+            }
+            KeyCode::Char('a') => self.start_edit(EditTarget::NewExtraText, String::new()),
+            KeyCode::Char('s') => match self
+                .lower_third
+                .lines
+                .iter()
+                .position(|line| line.kind == LineKind::SermonTitle)
+            {
+                Some(index) => {
+                    self.selected = index;
+                    let text = self.lower_third.lines[index].text.clone();
+                    self.start_edit(EditTarget::Line(index), text);
+                }
+                None => self.start_edit(EditTarget::NewSermonTitle, String::new()),
+            },
+            KeyCode::Char('d') | KeyCode::Delete => self.remove_line(self.selected),
+            KeyCode::Left => self.color = (self.color + COLOR_NAMES.len() - 1) % COLOR_NAMES.len(),
+            KeyCode::Right => self.color = (self.color + 1) % COLOR_NAMES.len(),
+            KeyCode::Char('[') => self.change_date(false),
+            KeyCode::Char(']') => self.change_date(true),
+            KeyCode::Char('y') => self.start_edit(EditTarget::YoutubeTitle, self.youtube_title()),
+            _ => {}
+        }
+        Action::Continue
+    }
+
+    fn handle_edit_key(&mut self, key: KeyEvent) {
+        let Some(edit) = &mut self.edit else {
+            return;
+        };
+        match key.code {
+            KeyCode::Char(character) => edit.text.push(character),
+            KeyCode::Backspace => {
+                edit.text.pop();
+            }
+            KeyCode::Esc => self.edit = None,
+            KeyCode::Enter => {
+                if let Some(edit) = self.edit.take() {
+                    self.finish_edit(edit);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn start_edit(&mut self, target: EditTarget, text: String) {
+        self.edit = Some(Edit { target, text });
+    }
+
+    // an empty line is removed, an empty YouTube title goes back to the generated one
+    fn finish_edit(&mut self, edit: Edit) {
+        let text = edit.text.trim().to_string();
+        match edit.target {
+            EditTarget::YoutubeTitle => {
+                self.youtube_title = Some(text).filter(|text| !text.is_empty());
+            }
+            EditTarget::Line(index) if text.is_empty() => self.remove_line(index),
+            EditTarget::Line(index) => self.lower_third.lines[index].text = text,
+            EditTarget::NewExtraText | EditTarget::NewSermonTitle if text.is_empty() => {}
+            EditTarget::NewExtraText => {
+                let index = if self.lower_third.lines.is_empty() {
+                    0
+                } else {
+                    self.selected + 1
+                };
+                self.selected = self.lower_third.insert(index, LineKind::ExtraText, text);
+            }
+            EditTarget::NewSermonTitle => {
+                // right after the church date, like the setup does it
+                let index = self
+                    .lower_third
+                    .lines
+                    .iter()
+                    .position(|line| line.kind == LineKind::ChurchDate)
+                    .map_or(0, |index| index + 1);
+                self.selected = self.lower_third.insert(index, LineKind::SermonTitle, text);
+            }
+        }
+    }
+
+    fn remove_line(&mut self, index: usize) {
+        self.lower_third.remove(index);
+        self.selected = self
+            .selected
+            .min(self.lower_third.lines.len().saturating_sub(1));
+    }
+
+    fn move_line(&mut self, down: bool) {
+        let other = if down {
+            self.selected + 1
+        } else {
+            match self.selected.checked_sub(1) {
+                Some(other) => other,
+                None => return,
+            }
+        };
+        if other < self.lower_third.lines.len() {
+            self.selected = self.lower_third.move_line(self.selected, other);
+        }
+    }
+
+    // the church date before or after, what was changed starts over
+    fn change_date(&mut self, later: bool) {
+        let tradition = self.config.tradition;
+        let date = if later {
+            next_church_date(self.church_date.date + Duration::days(1), Short, tradition)
+                .map(|church_date| church_date.date)
+        } else {
+            // there's a church date every Sunday, so it's never far back
+            (1..=7)
+                .map(|days| self.church_date.date - Duration::days(days))
+                .find(|date| {
+                    next_church_date(*date, Short, tradition)
+                        .is_ok_and(|church_date| church_date.date == *date)
+                })
+                .ok_or(Error::NoChurchDate(self.church_date.date))
+        };
+        match date
+            .and_then(|date| App::for_date(self.config, self.plan, self.recurring_lines, date))
+        {
+            Ok(app) => *self = app,
+            Err(err) => self.message = Some(err.to_string()),
+        }
+    }
+
+    pub fn render(&self, frame: &mut Frame) {
+        let [header_area, color_area, lines_area, youtube_area, help_area] = Layout::vertical([
+            Constraint::Length(4),
+            Constraint::Length(3),
+            Constraint::Min(4),
+            Constraint::Length(3),
+            Constraint::Length(2),
+        ])
+        .areas(frame.area());
+        let steps = &self.config.steps;
+        let turned_off = |on: bool| {
+            if on {
+                ""
+            } else {
+                " (turned off in the settings)"
+            }
+        };
+
+        let mut header = vec![Line::from(
+            format!(
+                "{}: {}",
+                self.church_date.date.format("%A, %B %-d, %Y"),
+                self.church_date_long.text
+            )
+            .bold(),
+        )];
+        header.push(if self.church_date.date != self.target_date {
+            Line::from(format!(
+                "There isn't a church date on {}, so the next one is used.",
+                self.target_date.format("%A, %B %-d")
+            ))
+        } else {
+            Line::from(format!(
+                "{}, the color is {}.",
+                self.church_date.season, self.church_date.color
+            ))
+        });
+        frame.render_widget(
+            Paragraph::new(header).block(Block::bordered().title(" Church Date Updater ")),
+            header_area,
+        );
+
+        let color_name = COLOR_NAMES[self.color];
+        let mut color = vec![
+            Span::styled("      ", Style::new().bg(swatch_color(color_name))),
+            Span::raw(format!(" {color_name}")),
+        ];
+        if color_name != self.church_date.color.to_string() {
+            color.push(format!(" (suggested: {})", self.church_date.color).dim());
+        }
+        frame.render_widget(
+            Paragraph::new(Line::from(color))
+                .block(Block::bordered().title(format!(" Color{} ", turned_off(steps.color)))),
+            color_area,
+        );
+
+        let items: Vec<ListItem> = self
+            .lower_third()
+            .into_iter()
+            .zip(&self.lower_third.lines)
+            .map(|(text, line)| {
+                let kind = match line.kind {
+                    LineKind::ChurchDate => "  church date",
+                    LineKind::SermonTitle => "  sermon title",
+                    LineKind::ExtraText => "",
+                    LineKind::Standard => "  every week",
+                };
+                ListItem::new(Line::from(vec![Span::raw(text), kind.dim()]))
+            })
+            .collect();
+        let mut list_state = ListState::default().with_selected(Some(self.selected));
+        frame.render_stateful_widget(
+            List::new(items)
+                .block(
+                    Block::bordered()
+                        .title(format!(" Lower third{} ", turned_off(steps.lower_third))),
+                )
+                .highlight_style(Style::new().reversed())
+                .highlight_symbol("> "),
+            lines_area,
+            &mut list_state,
+        );
+
+        let youtube_title = self.youtube_title();
+        let length = youtube_title.chars().count();
+        let mut youtube_block = Block::bordered().title(format!(
+            " YouTube title ({length}/{YOUTUBE_TITLE_LIMIT}){}{} ",
+            if self.youtube_title.is_some() {
+                ", typed"
+            } else {
+                ""
+            },
+            turned_off(steps.youtube)
+        ));
+        if length > YOUTUBE_TITLE_LIMIT {
+            youtube_block = youtube_block.red();
+        }
+        frame.render_widget(
+            Paragraph::new(youtube_title).block(youtube_block),
+            youtube_area,
+        );
+
+        let (status, help) = match &self.edit {
+            Some(edit) => {
+                let label = match edit.target {
+                    EditTarget::YoutubeTitle => "YouTube title",
+                    EditTarget::NewSermonTitle => "Sermon title",
+                    EditTarget::NewExtraText => "New line",
+                    EditTarget::Line(index) => match self.lower_third.lines[index].kind {
+                        LineKind::ChurchDate => "Church date",
+                        LineKind::SermonTitle => "Sermon title",
+                        LineKind::ExtraText | LineKind::Standard => "Line",
+                    },
+                };
+                (
+                    Line::from(format!("{label}: {}_", edit.text)),
+                    "Enter keeps it, Esc cancels, empty removes the line",
+                )
+            }
+            None => (
+                Line::from(self.status().unwrap_or_default().yellow()),
+                "↑/↓ select  K/J move  e edit  a add  s sermon title  d delete  ←/→ color  [/] date  y YouTube title  p publish  q quit",
+            ),
+        };
+        frame.render_widget(
+            Paragraph::new(vec![status, Line::from(help.dim())]),
+            help_area,
+        );
+    }
+
+    // the last message, or what's wrong with the sermon title
+    fn status(&self) -> Option<String> {
+        if self.message.is_some() {
+            return self.message.clone();
+        }
+        let mut preachers = self.config.sermon_titles.preachers.clone();
+        preachers.push(self.preacher.clone());
+        preachers.retain(|preacher| !preacher.is_empty());
+        let problems = check_sermon_title(
+            self.sermon_title(),
+            &preachers,
+            self.config.sermon_titles.max_length,
+        );
+        problems
+            .first()
+            .map(|problem| format!("The sermon title {problem}."))
+    }
+
+    fn event_loop(&mut self, terminal: &mut DefaultTerminal) -> io::Result<Option<Choices>> {
+        loop {
+            terminal.draw(|frame| self.render(frame))?;
+            if let Event::Key(key) = event::read()? {
+                // Windows sends key releases too
+                if key.kind != KeyEventKind::Press {
+                    continue;
+                }
+                match self.handle_key(key) {
+                    Action::Continue => {}
+                    Action::Publish => return Ok(Some(self.choices())),
+                    Action::Quit => return Ok(None),
+                }
+            }
+        }
+    }
+}
+
+fn color_position(name: &str) -> Option<usize> {
+    COLOR_NAMES
+        .iter()
+        .position(|color_name| color_name.eq_ignore_ascii_case(name))
+}
+
+fn swatch_color(name: &str) -> TerminalColor {
+    match name {
+        "White" => TerminalColor::White,
+        "Green" => TerminalColor::Green,
+        "Purple" => TerminalColor::Magenta,
+        "Red" => TerminalColor::Red,
+        "Yellow" => TerminalColor::Yellow,
+        "Blue" => TerminalColor::Blue,
+        "Black" => TerminalColor::Black,
+        _ => TerminalColor::Reset,
+    }
+}
+
+// the full-screen setup, returns what to publish or nothing when it's closed
+pub fn run(
+    config: &Config,
+    plan: Option<&Plan>,
+    recurring_lines: &[String],
+    target_date: NaiveDate,
+    message: Option<String>,
+) -> Result<Option<Choices>, Error> {
+    let mut app = App::for_date(config, plan, recurring_lines, target_date)?;
+    app.message = message;
+    let mut terminal = ratatui::try_init().map_err(Error::Prompt)?;
+    let result = app.event_loop(&mut terminal);
+    ratatui::restore();
+    result.map_err(Error::Prompt)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::backend::TestBackend;
+    use ratatui::Terminal;
+
+    fn date() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 11, 8).unwrap()
+    }
+
+    fn press(app: &mut App, keys: &str) {
+        for character in keys.chars() {
+            let code = match character {
+                '\n' => KeyCode::Enter,
+                character => KeyCode::Char(character),
+            };
+            app.handle_key(KeyEvent::from(code));
+        }
+    }
+
+    fn erase(app: &mut App, count: usize) {
+        for _ in 0..count {
+            app.handle_key(KeyEvent::from(KeyCode::Backspace));
+        }
+    }
+
+    #[test]
+    fn editing_lines() {
+        let config = Config::default();
+        let mut app = App::for_date(&config, None, &[], date()).unwrap();
+        let church_date_line = app.church_date_line();
+        assert_eq!(app.lower_third(), vec![church_date_line.clone()]);
+
+        press(&mut app, "aPotluck\n");
+        press(&mut app, "sAll Are Welcome\n");
+        assert_eq!(
+            app.lower_third(),
+            vec![
+                church_date_line.clone(),
+                "\"All Are Welcome\"".to_string(),
+                "Potluck".to_string()
+            ]
+        );
+        assert!(app.youtube_title().starts_with("Potluck - "));
+
+        // the sermon title is selected, move it to the end and remove the church date
+        press(&mut app, "Jkkd");
+        assert_eq!(
+            app.lower_third(),
+            vec!["Potluck".to_string(), "\"All Are Welcome\"".to_string()]
+        );
+
+        // an empty edit removes the line
+        press(&mut app, "e");
+        erase(&mut app, "Potluck".len());
+        press(&mut app, "\n");
+        assert_eq!(app.lower_third(), vec!["\"All Are Welcome\"".to_string()]);
+        assert!(app.youtube_title().starts_with("\"All Are Welcome\" - "));
+    }
+
+    #[test]
+    fn youtube_title_and_color() {
+        let config = Config::default();
+        let mut app = App::for_date(&config, None, &[], date()).unwrap();
+        let suggested = app.color;
+
+        // the generated title is there to change
+        let generated = app.youtube_title();
+        press(&mut app, "y");
+        erase(&mut app, generated.chars().count());
+        press(&mut app, "Morning Worship\n");
+        assert_eq!(app.youtube_title(), "Morning Worship");
+        // typing while editing doesn't publish or quit
+        press(&mut app, "yqp");
+        assert!(app.edit.is_some());
+        app.handle_key(KeyEvent::from(KeyCode::Esc));
+        assert_eq!(app.youtube_title(), "Morning Worship");
+
+        app.handle_key(KeyEvent::from(KeyCode::Left));
+        assert_eq!(
+            app.color,
+            (suggested + COLOR_NAMES.len() - 1) % COLOR_NAMES.len()
+        );
+        assert_eq!(
+            app.handle_key(KeyEvent::from(KeyCode::Char('p'))),
+            Action::Publish
+        );
+        let choices = app.choices();
+        assert_eq!(choices.youtube_title, "Morning Worship");
+        assert_eq!(choices.color, COLOR_NAMES[app.color]);
+        assert_eq!(choices.target_date, date());
+    }
+
+    #[test]
+    fn changing_the_date() {
+        let config = Config::default();
+        let mut app = App::for_date(&config, None, &[], date()).unwrap();
+        press(&mut app, "]");
+        assert_eq!(app.church_date.date, date() + Duration::days(7));
+        press(&mut app, "[[");
+        assert_eq!(app.church_date.date, date() - Duration::days(7));
+    }
+
+    #[test]
+    fn preparing_on_a_weekday() {
+        // the Monday before, everything has the Sunday's date
+        let config = Config::default();
+        let app = App::for_date(&config, None, &[], date() - Duration::days(6)).unwrap();
+        assert_eq!(app.church_date.date, date());
+        assert!(app.church_date_line().starts_with("November 8th, "));
+        assert!(app.youtube_title().starts_with("11-08-26 - "));
+    }
+
+    #[test]
+    fn rendering() {
+        let config = Config::default();
+        let app = App::for_date(&config, None, &[], date()).unwrap();
+        let mut terminal = Terminal::new(TestBackend::new(100, 20)).unwrap();
+        terminal.draw(|frame| app.render(frame)).unwrap();
+        let screen: String = terminal
+            .backend()
+            .buffer()
+            .content
+            .iter()
+            .map(|cell| cell.symbol())
+            .collect();
+        assert!(screen.contains(&app.church_date_long.text));
+        assert!(screen.contains(&app.youtube_title()));
+    }
+}
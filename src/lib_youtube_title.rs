@@ -1,30 +1,55 @@
+#[cfg(feature = "youtube")]
 extern crate google_youtube3 as youtube3;
+#[cfg(feature = "youtube")]
 extern crate hyper;
+#[cfg(feature = "youtube")]
 extern crate hyper_rustls;
 
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "youtube")]
 use std::default::Default;
+#[cfg(feature = "youtube")]
+use std::path::Path;
+#[cfg(feature = "youtube")]
 use youtube3::api::{LiveBroadcast, LiveBroadcastSnippet};
-use youtube3::{oauth2, Error, YouTube};
+#[cfg(feature = "youtube")]
+use youtube3::{oauth2, YouTube};
 
-use dialoguer::{theme::ColorfulTheme, Confirm};
+#[cfg(feature = "youtube")]
+use crate::lib_error::Error;
 
-#[tokio::main]
-pub async fn update_youtube_title(new_title: String) {
-    let client_secret = "secret.json"; // enter file path to API client secret here
+#[cfg(feature = "youtube")]
+type Hub = YouTube<hyper_rustls::HttpsConnector<hyper::client::HttpConnector>>;
+
+// what was changed, so it can be changed back, the same in the history file
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+pub struct TitleChange {
+    pub broadcast_id: String,
+    pub old_title: String,
+    pub new_title: String,
+}
+
+#[cfg(feature = "youtube")]
+async fn connect(client_secret: &Path, token_cache: &Path) -> Result<Hub, Error> {
     let secret = oauth2::read_application_secret(client_secret)
         .await
-        .expect(client_secret);
+        .map_err(|err| {
+            Error::Auth(format!(
+                "Unable to read '{}': {err}",
+                client_secret.display()
+            ))
+        })?;
 
     let auth = oauth2::InstalledFlowAuthenticator::builder(
         secret,
         oauth2::InstalledFlowReturnMethod::HTTPRedirect,
     )
-    .persist_tokens_to_disk("tokencache.json")
+    .persist_tokens_to_disk(token_cache)
     .build()
     .await
-    .unwrap();
+    .map_err(|err| Error::Auth(err.to_string()))?;
 
-    let hub = YouTube::new(
+    Ok(YouTube::new(
         hyper::Client::builder().build(
             hyper_rustls::HttpsConnectorBuilder::new()
                 .with_native_roots()
@@ -34,78 +59,31 @@ pub async fn update_youtube_title(new_title: String) {
                 .build(),
         ),
         auth,
-    );
-
-    /*
-     * Get Broadcast ID
-     */
-    //I need to initialize something here, but this should in no way be used
-    let mut broadcast_id = String::new();
-
-    let mut retry = true;
+    ))
+}
 
-    //Loop over it until something sets 'retry' to false.
-    while retry {
-        //Check for a current YouTube livestream
-        let broadcasts_list_active = hub
-            .live_broadcasts()
-            .list(&vec!["id".into()])
-            .broadcast_status("active")
-            .doit()
-            .await
-            .unwrap()
-            .1;
-
-        //If there is a current YouTube livestream, use it. if there are none, it will return an empty vector so unwrap can be used. Set retry to false
-        let mut id_vec = broadcasts_list_active.items.unwrap();
-
-        #[allow(clippy::if_not_else)]
-        if !id_vec.is_empty() {
-            retry = false;
-            println!("Using active stream ID.");
-            let id_ref = id_vec[0].id.as_ref();
-            broadcast_id = String::from(id_ref.unwrap());
-        }
-        //Check for upcoming live stream
-        else {
-            let broadcasts_list_upcoming = hub
-                .live_broadcasts()
-                .list(&vec!["id".into()])
-                .broadcast_status("upcoming")
-                .doit()
-                .await
-                .unwrap()
-                .1;
-
-            id_vec = broadcasts_list_upcoming.items.unwrap();
-
-            if !id_vec.is_empty() {
-                retry = false;
-                println!("Using upcoming ID.");
-                let id_ref = id_vec[0].id.as_ref();
-                broadcast_id = String::from(id_ref.unwrap());
-            } else {
-                //Ask to try again to find the YouTube ID
-                if Confirm::with_theme(&ColorfulTheme::default())
-                    .with_prompt("Couldn't find any YouTube IDs. Try dismissing anything in the YouTube studio before trying again. Would you like to try again?".to_string())
-                    .default(true)
-                    .interact()
-                    .unwrap()
-                {
-                    //Nothing needs to be done to retry
-                } else {
-                    println!("Not updating the YouTube title.");
-                    return;
-                }
-            }
+// signing in again fixes missing tokens, the rest is up to YouTube
+#[cfg(feature = "youtube")]
+fn youtube_error(e: youtube3::Error) -> Error {
+    match e {
+        youtube3::Error::MissingAPIKey | youtube3::Error::MissingToken(_) => {
+            Error::Auth(e.to_string())
         }
+        youtube3::Error::HttpError(_)
+        | youtube3::Error::Io(_)
+        | youtube3::Error::Cancelled
+        | youtube3::Error::UploadSizeLimitExceeded(_, _)
+        | youtube3::Error::Failure(_)
+        | youtube3::Error::BadRequest(_)
+        | youtube3::Error::FieldClash(_)
+        | youtube3::Error::JsonDecodeError(_, _) => Error::Api(e.to_string()),
     }
+}
 
-    /*
-     * Update title
-     */
+#[cfg(feature = "youtube")]
+async fn set_title(hub: &Hub, broadcast_id: String, title: String) -> Result<(), Error> {
     let snippet = LiveBroadcastSnippet {
-        title: Some(new_title),
+        title: Some(title),
         ..Default::default()
     };
 
@@ -115,27 +93,114 @@ pub async fn update_youtube_title(new_title: String) {
         ..Default::default()
     };
 
-    let result = hub
-        .live_broadcasts()
+    hub.live_broadcasts()
         .update(req)
         .add_part("id")
         .add_part("snippet")
         .doit()
-        .await;
-
-    match result {
-        Err(e) => match e {
-            Error::HttpError(_)
-            | Error::Io(_)
-            | Error::MissingAPIKey
-            | Error::MissingToken(_)
-            | Error::Cancelled
-            | Error::UploadSizeLimitExceeded(_, _)
-            | Error::Failure(_)
-            | Error::BadRequest(_)
-            | Error::FieldClash(_)
-            | Error::JsonDecodeError(_, _) => println!("Unable to update the YouTube title: {e}"),
-        },
-        Ok(_) => println!("Successfully updated the YouTube title."),
+        .await
+        .map(|_| ())
+        .map_err(youtube_error)
+}
+
+// the id and title of the first broadcast with the status, if there is one
+#[cfg(feature = "youtube")]
+async fn find_broadcast(hub: &Hub, status: &str) -> Result<Option<(String, String)>, Error> {
+    let broadcasts = hub
+        .live_broadcasts()
+        .list(&vec!["id".into(), "snippet".into()])
+        .broadcast_status(status)
+        .doit()
+        .await
+        .map_err(youtube_error)?
+        .1;
+
+    Ok(broadcasts
+        .items
+        .unwrap_or_default()
+        .into_iter()
+        .find_map(|broadcast| {
+            let title = broadcast
+                .snippet
+                .and_then(|snippet| snippet.title)
+                .unwrap_or_default();
+            broadcast.id.map(|id| (id, title))
+        }))
+}
+
+// returns what was changed, nothing when the title wasn't updated; when there
+// isn't a broadcast `try_again` is asked if it should look again, without it
+// it gives up right away
+#[cfg(feature = "youtube")]
+#[tokio::main]
+pub async fn update_youtube_title(
+    new_title: String,
+    client_secret: &Path,
+    token_cache: &Path,
+    try_again: Option<&dyn Fn() -> Result<bool, Error>>,
+    dry_run: bool,
+) -> Result<Option<TitleChange>, Error> {
+    // signing in saves the token cache, even when it's only refreshed, and a
+    // dry run doesn't write anything
+    if dry_run {
+        println!("Would change the title of the live or upcoming broadcast to '{new_title}'.");
+        return Ok(None);
     }
+    let hub = connect(client_secret, token_cache).await?;
+
+    /*
+     * Get Broadcast ID
+     */
+    //Loop over it until a broadcast is found or they don't want to try again.
+    let (broadcast_id, old_title) = loop {
+        //Check for a current YouTube livestream
+        if let Some(broadcast) = find_broadcast(&hub, "active").await? {
+            println!("Using active stream ID.");
+            break broadcast;
+        }
+        //Check for upcoming live stream
+        if let Some(broadcast) = find_broadcast(&hub, "upcoming").await? {
+            println!("Using upcoming ID.");
+            break broadcast;
+        }
+
+        //Ask to try again to find the YouTube ID
+        let retry = match try_again {
+            Some(try_again) => try_again()?,
+            None => {
+                println!("Couldn't find any YouTube IDs.");
+                false
+            }
+        };
+        if !retry {
+            println!("Not updating the YouTube title.");
+            return Ok(None);
+        }
+    };
+
+    /*
+     * Update title
+     */
+    set_title(&hub, broadcast_id.clone(), new_title.clone()).await?;
+    println!("Successfully updated the YouTube title.");
+    Ok(Some(TitleChange {
+        broadcast_id,
+        old_title,
+        new_title,
+    }))
+}
+
+// for undo, the broadcast might not be live anymore so it isn't looked up
+#[cfg(feature = "youtube")]
+#[tokio::main]
+pub async fn restore_youtube_title(
+    broadcast_id: String,
+    title: String,
+    client_secret: &Path,
+    token_cache: &Path,
+) -> Result<(), Error> {
+    let hub = connect(client_secret, token_cache).await?;
+    set_title(&hub, broadcast_id, title).await?;
+    println!("Successfully restored the YouTube title.");
+    Ok(())
 }
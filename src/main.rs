@@ -1,47 +1,750 @@
-use chrono::{Datelike, Local};
+use chrono::{Datelike, Duration, Local, NaiveDate, Utc};
+use clap::Parser;
 use colored::Colorize;
 use dialoguer::{theme::ColorfulTheme, Confirm, Input, Select};
 use std::fs;
-use std::fs::File;
-use std::io::{BufWriter, Write};
+use std::path::Path;
+use std::process::ExitCode;
 
-mod lib_church_date;
-use lib_church_date::Color;
-use lib_church_date::OrdinalLength::{Long, Short};
-use lib_church_date::{date_ordinal, next_church_date};
+use church_date_updater::lib_church_date::Color;
+use church_date_updater::lib_church_date::OrdinalLength::{Long, Short};
+use church_date_updater::lib_church_date::{church_dates_between, next_church_date, Tradition};
+use church_date_updater::lib_easter::{EasterError, FIRST_GREGORIAN_YEAR, LAST_YEAR};
+use church_date_updater::lib_error::Error;
+use church_date_updater::lib_export::{export, ExportFormat};
+use church_date_updater::lib_sermon_title::{check_sermon_title, corrected_sermon_title};
+use church_date_updater::lib_template::Template;
+use church_date_updater::lib_youtube_title::TitleChange;
 
-mod lib_youtube_title;
-use lib_youtube_title::update_youtube_title;
+#[cfg(feature = "youtube")]
+use church_date_updater::lib_youtube_title::{restore_youtube_title, update_youtube_title};
 
-fn main() {
+mod lib_calendar_view;
+use lib_calendar_view::{print_church_date, print_month, print_range, print_year};
+
+mod lib_cli_args;
+use lib_cli_args::{Args, Command, PublishArgs, COLOR_NAMES};
+
+mod lib_config;
+use lib_config::{
+    data_directory, load_config, Config, Paths, SermonTitles, TitleValues, YOUTUBE_TITLE_LIMIT,
+};
+
+mod lib_history;
+use lib_history::{append_history, read_history, undo_target, HistoryEntry};
+
+mod lib_init;
+use lib_init::init;
+
+mod lib_lower_third;
+use lib_lower_third::{
+    edit_lower_third, read_recurring_lines, write_lower_data, write_recurring_lines, LineKind,
+    LowerThird, LowerThirdData,
+};
+
+mod lib_output;
+use lib_output::{copy_output, write_output};
+
+mod lib_overlay;
+use lib_overlay::{overlay_path, write_overlay};
+
+mod lib_plan;
+use lib_plan::{load_plan, PlannedService};
+
+#[cfg(feature = "obs-websocket")]
+mod lib_obs;
+#[cfg(feature = "obs-websocket")]
+use lib_obs::update_obs;
+
+#[cfg(feature = "server")]
+mod lib_server;
+#[cfg(feature = "server")]
+use lib_server::serve;
+
+#[cfg(feature = "tui")]
+mod lib_tui;
+
+#[cfg(test)]
+mod lib_test_directory;
+
+// tell the user which cargo feature was left out of this build
+#[cfg(any(
+    not(feature = "youtube"),
+    not(feature = "tui"),
+    not(feature = "server"),
+    not(feature = "obs-websocket")
+))]
+fn missing_feature(feature: &str, skipped: &str) {
+    println!(
+        "{skipped} This copy of Church Date Updater was built without the '{feature}' feature."
+    );
+    println!("To use it, rebuild with `cargo build -r --features {feature}`.");
+}
+
+// what went wrong and what can be done about it, on stderr so it doesn't end
+// up in exported files
+fn print_error(err: &Error) {
+    eprintln!("{}", err.to_string().red());
+    match err {
+        Error::Easter(_) | Error::NoChurchDate(_) => eprintln!(
+            "Church dates can only be found for the years {FIRST_GREGORIAN_YEAR} to {LAST_YEAR}."
+        ),
+        Error::Io { .. } => {
+            eprintln!("Check that the folder is there and the file isn't open in another program.");
+            eprintln!("`church-date-updater init` puts the files where they're expected.");
+        }
+        Error::Prompt(_) => {
+            eprintln!("The question couldn't be answered, maybe the window was closed.");
+            eprintln!(
+                "To run without questions, use --yes and give the answers as command line options."
+            );
+        }
+        Error::Settings(_) => {
+            eprintln!("Fix the settings or the plan, README.MD has examples of both.")
+        }
+        Error::Auth(_) => {
+            eprintln!("Check the key from the YouTube Data API (secret.json).");
+            eprintln!("Deleting the token cache (tokencache.json) signs in to YouTube again.");
+        }
+        Error::Api(_) => eprintln!(
+            "Check the internet connection and the live stream in YouTube Studio, then try again."
+        ),
+        Error::Obs(_) => {
+            eprintln!("Check that OBS is open and its WebSocket server is turned on (Tools, WebSocket Server Settings).");
+            eprintln!("The host, port, password and source names are in [obs] in the settings.");
+        }
+        Error::Server(_) => {
+            eprintln!("Another program, or another `serve`, might be using the port.");
+            eprintln!("Another port can be set with `serve --port` or `port` in [server] in the settings.");
+        }
+    }
+}
+
+// so a scheduled task or script can tell what went wrong, see README.MD
+fn exit_code(err: &Error) -> ExitCode {
+    ExitCode::from(match err {
+        Error::Easter(_) | Error::NoChurchDate(_) => 3,
+        Error::Io { .. } => 4,
+        Error::Prompt(_) => 5,
+        Error::Settings(_) => 6,
+        Error::Auth(_) => 7,
+        Error::Api(_) => 8,
+        Error::Obs(_) => 9,
+        Error::Server(_) => 10,
+    })
+}
+
+// `cal`, `cal -y`, `cal <year>` or `cal <month> <year>`, like the unix `cal`
+fn print_calendar(args: &[String], tradition: Tradition) -> Result<(), Error> {
+    let today = Local::now().date_naive();
+
+    let parsed_year = |year: &String| year.parse::<i32>().ok();
+    // church dates can't be found outside of these years, and some of them
+    // aren't even dates
+    let checked_year = |year: i32| {
+        if (FIRST_GREGORIAN_YEAR..=LAST_YEAR).contains(&year) {
+            Ok(year)
+        } else {
+            Err(Error::Easter(EasterError::OutOfRange {
+                year,
+                first: FIRST_GREGORIAN_YEAR,
+                last: LAST_YEAR,
+            }))
+        }
+    };
+    let parsed_month = |month: &String| {
+        month
+            .parse::<u32>()
+            .ok()
+            .filter(|month| (1..=12).contains(month))
+    };
+
+    let print_usage = || {
+        println!(
+            "Usage: church-date-updater calendar [-y | <year> | <month> <year> | --from <date> --to <date>]"
+        );
+        Ok(())
+    };
+
+    match args {
+        [] => print_month(today.year(), today.month(), tradition),
+        [flag] if flag == "-y" => print_year(today.year(), tradition),
+        [year] => match parsed_year(year) {
+            Some(year) => print_year(checked_year(year)?, tradition),
+            None => print_usage(),
+        },
+        [month, year] => match (parsed_month(month), parsed_year(year)) {
+            (Some(month), Some(year)) => print_month(checked_year(year)?, month, tradition),
+            _ => print_usage(),
+        },
+        _ => print_usage(),
+    }
+}
+
+// ask which service is being prepared, it doesn't have to be today's
+fn ask_target_date(tradition: Tradition) -> Result<NaiveDate, Error> {
+    let today = Local::now().date_naive();
+
+    let mut dates = vec![today];
+    let mut items = vec![format!("Today ({})", today.format("%A, %B %-d"))];
+
+    // the next service after today, for preparing everything ahead of time
+    if let Ok(church_date) = next_church_date(today + Duration::days(1), Short, tradition) {
+        dates.push(church_date.date);
+        items.push(format!(
+            "{}: {}",
+            church_date.date.format("%A, %B %-d"),
+            church_date.text
+        ));
+    }
+
+    items.push("Another date".to_string());
+
+    let selection = Select::with_theme(&ColorfulTheme::default())
+        .with_prompt("Which service are you preparing for?")
+        .default(0)
+        .items(&items)
+        .interact()
+        .map_err(Error::Prompt)?;
+
+    if let Some(date) = dates.get(selection) {
+        return Ok(*date);
+    }
+
+    let date: String = Input::with_theme(&ColorfulTheme::default())
+        .with_prompt("Okay, what is the date? (YYYY-MM-DD)")
+        .validate_with(|input: &String| {
+            NaiveDate::parse_from_str(input, "%Y-%m-%d")
+                .map(|_| ())
+                .map_err(|_| "That date should look like 2026-12-24.")
+        })
+        .interact_text()
+        .map_err(Error::Prompt)?;
+    NaiveDate::parse_from_str(&date, "%Y-%m-%d")
+        .map_err(|err| Error::Prompt(std::io::Error::new(std::io::ErrorKind::InvalidInput, err)))
+}
+
+fn print_today(tradition: Tradition) -> Result<(), Error> {
+    let today = Local::now().date_naive();
+    let church_date = next_church_date(today, Short, tradition)?;
+    if church_date.date != today {
+        println!("There isn't a church date today, the next one is:");
+    }
+    print_church_date(&church_date);
+    Ok(())
+}
+
+fn print_next(count: usize, tradition: Tradition) -> Result<(), Error> {
+    let mut day = Local::now().date_naive();
+    for _ in 0..count {
+        let church_date = next_church_date(day + Duration::days(1), Short, tradition)?;
+        print_church_date(&church_date);
+        day = church_date.date;
+    }
+    Ok(())
+}
+
+fn export_church_dates(
+    format: ExportFormat,
+    from: Option<NaiveDate>,
+    to: Option<NaiveDate>,
+    output: Option<&Path>,
+    tradition: Tradition,
+) -> Result<(), Error> {
+    // January 1st and December 31st of this year
+    let today = Local::now().date_naive();
+    let days_in_year = if today.leap_year() { 366 } else { 365 };
+    let from = from.unwrap_or(today - Duration::days(today.ordinal0().into()));
+    let to = to.unwrap_or(today + Duration::days((days_in_year - today.ordinal()).into()));
+
+    let church_dates = church_dates_between(from, to, Short, tradition)?;
+    let exported = export(&church_dates, format, Utc::now().naive_utc());
+
+    match output {
+        Some(output) => {
+            write_output(output, exported.as_bytes()).map_err(|err| Error::io(output, err))?;
+            println!(
+                "Exported {} church dates to '{}'.",
+                church_dates.len(),
+                output.display()
+            );
+        }
+        None => print!("{exported}"),
+    }
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    let args = Args::parse();
+
+    let (mut config, config_location) = match load_config(args.profile.as_deref()) {
+        Ok(loaded) => loaded,
+        Err(err) => {
+            let err = Error::Settings(err);
+            print_error(&err);
+            return exit_code(&err);
+        }
+    };
+    let data_directory = match &args.command {
+        // written into the settings, where a relative folder would be read as
+        // relative to the settings instead of where `init` was run
+        Some(Command::Init {
+            directory: Some(directory),
+        }) => std::path::absolute(directory).unwrap_or_else(|_| directory.clone()),
+        _ => data_directory(&config.paths, config_location.as_deref()),
+    };
+    config.paths = config.paths.resolved(&data_directory);
+    let tradition = config.tradition;
+    let publishing = matches!(
+        args.command,
+        None | Some(Command::Publish(_)) | Some(Command::Tui { .. })
+    );
+
+    let result = match args.command {
+        None => publish(
+            &args.publish,
+            &config,
+            config_location.as_deref(),
+            args.profile.as_deref(),
+        ),
+        Some(Command::Publish(publish_args)) => publish(
+            &publish_args,
+            &config,
+            config_location.as_deref(),
+            args.profile.as_deref(),
+        ),
+        Some(Command::Tui { date, dry_run }) => publish_tui(
+            &config,
+            config_location.as_deref(),
+            args.profile.as_deref(),
+            date,
+            dry_run,
+        ),
+        Some(Command::Today) => print_today(tradition),
+        Some(Command::Next { count }) => print_next(count, tradition),
+        Some(Command::Calendar {
+            from: Some(from),
+            to: Some(to),
+            ..
+        }) => print_range(from, to, tradition),
+        Some(Command::Calendar { args, .. }) => print_calendar(&args, tradition),
+        Some(Command::Export {
+            format,
+            from,
+            to,
+            output,
+        }) => export_church_dates(format, from, to, output.as_deref(), tradition),
+        Some(Command::History { count }) => print_history(&config.paths, count),
+        Some(Command::Undo { yes }) => undo(&config, yes),
+        Some(Command::Init { .. }) => init(&config.paths, config_location.as_deref()),
+        Some(Command::Serve { port }) => serve(&config, port.unwrap_or(config.server.port)),
+    };
+
+    let code = match &result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            print_error(err);
+            exit_code(err)
+        }
+    };
+
+    //wait a little bit so they can read the text
+    if publishing {
+        std::thread::sleep(std::time::Duration::from_secs(config.steps.wait_seconds));
+    }
+    code
+}
+
+fn print_history_entry(entry: &HistoryEntry) {
+    let time = entry.time.format("%Y-%m-%d %H:%M:%S");
+    match (entry.undoes, entry.target_date) {
+        (Some(undone), _) => println!("{time} undid {}", undone.format("%Y-%m-%d %H:%M:%S")),
+        (None, Some(target_date)) => println!("{time} for {}", target_date.format("%a %b %e %Y")),
+        (None, None) => println!("{time}"),
+    }
+    if let Some(lower_third) = &entry.lower_third {
+        println!("    Lower third: {}", lower_third.join(" / "));
+    }
+    if let Some(color) = &entry.color {
+        println!("    Color: {color}");
+    }
+    if let Some(youtube) = &entry.youtube {
+        println!(
+            "    YouTube: '{}' to '{}' (broadcast {})",
+            youtube.old_title, youtube.new_title, youtube.broadcast_id
+        );
+    }
+}
+
+fn print_history(paths: &Paths, count: usize) -> Result<(), Error> {
+    let entries = read_history(&paths.history)?;
+    if entries.is_empty() {
+        println!("Nothing has been published yet.");
+    }
+    for entry in entries.iter().skip(entries.len().saturating_sub(count)) {
+        print_history_entry(entry);
+    }
+    Ok(())
+}
+
+// put back what the last run changed, the undo is saved to the history too so
+// the next undo goes back one more run
+fn undo(config: &Config, yes: bool) -> Result<(), Error> {
+    let paths = &config.paths;
+    let entries = read_history(&paths.history)?;
+    let Some((target, previous_color)) = undo_target(&entries) else {
+        println!("There isn't anything to undo.");
+        return Ok(());
+    };
+
+    println!("The last run was:");
+    print_history_entry(target);
+    if !yes
+        && !Confirm::with_theme(&ColorfulTheme::default())
+            .with_prompt("Would you like to undo it?")
+            .default(true)
+            .interact()
+            .map_err(Error::Prompt)?
+    {
+        println!("Okay, not undoing anything.");
+        return Ok(());
+    }
+
+    // the first step that didn't work, the others are still undone
+    let mut failure = None;
+
+    let mut undo_entry = HistoryEntry {
+        time: Local::now().naive_local(),
+        undoes: Some(target.time),
+        target_date: None,
+        lower_third: None,
+        previous_lower_third: None,
+        color: None,
+        youtube: None,
+    };
+
+    if target.lower_third.is_some() {
+        if let Some(previous_lower_third) = &target.previous_lower_third {
+            match write_lower_data(
+                &paths.lower_third,
+                &paths.lower_third_json,
+                &LowerThirdData::new(previous_lower_third, &config.lower_third),
+            ) {
+                Ok(()) => {
+                    println!("Successfully restored the OBS lower third.");
+                    undo_entry.previous_lower_third = target.lower_third.clone();
+                    undo_entry.lower_third = Some(previous_lower_third.clone());
+                }
+                Err(err) => {
+                    println!("Unable to restore the OBS lower third: {err}");
+                    failure.get_or_insert(err);
+                }
+            }
+        } else {
+            println!("There wasn't an OBS lower third before, so it stays the same.");
+        }
+    }
+
+    if target.color.is_some() {
+        if let Some(previous_color) = previous_color {
+            let old_file = paths.image_directory.join(format!("{previous_color}.png"));
+            match copy_output(&old_file, &paths.color_image) {
+                Ok(()) => {
+                    println!("Successfully restored the color for OBS ({previous_color}).");
+                    undo_entry.color = Some(previous_color.to_string());
+                    if config.steps.overlay {
+                        if let Err(err) = publish_overlay(config, previous_color, false) {
+                            failure.get_or_insert(err);
+                        }
+                    }
+                }
+                Err(err) => {
+                    println!("Unable to restore the color for OBS: {err}");
+                    failure.get_or_insert(copy_error(&old_file, &paths.color_image, err));
+                }
+            }
+        } else {
+            println!("The color from before isn't in the history, so it stays the same.");
+        }
+    }
+
+    if config.steps.obs && (undo_entry.lower_third.is_some() || undo_entry.color.is_some()) {
+        if let Err(err) = publish_obs(
+            config,
+            undo_entry.lower_third.as_deref(),
+            undo_entry.color.as_deref(),
+            false,
+        ) {
+            failure.get_or_insert(err);
+        }
+    }
+
+    if let Some(youtube) = &target.youtube {
+        match undo_youtube(paths, youtube) {
+            Ok(change) => undo_entry.youtube = change,
+            Err(err) => {
+                println!("Unable to restore the YouTube title: {err}");
+                failure.get_or_insert(err);
+            }
+        }
+    }
+
+    if let Err(err) = append_history(&paths.history, &undo_entry) {
+        println!("Unable to save the undo to the history: {err}");
+        failure.get_or_insert(err);
+    }
+    failure.map_or(Ok(()), Err)
+}
+
+// either the color's image is missing or the one for OBS can't be replaced
+fn copy_error(from: &Path, to: &Path, err: std::io::Error) -> Error {
+    if from.is_file() {
+        Error::io(to, err)
+    } else {
+        Error::io(from, err)
+    }
+}
+
+// YouTube rejects titles that are too long, so a shorter one is offered first
+fn fitted_youtube_title(
+    youtube_title: String,
+    title_values: &TitleValues,
+    template: &Template,
+    yes: bool,
+) -> Result<String, Error> {
+    let length = youtube_title.chars().count();
+    if length <= YOUTUBE_TITLE_LIMIT {
+        return Ok(youtube_title);
+    }
+    println!(
+        "{}",
+        format!(
+            "The YouTube title is {length} characters long, YouTube only allows {YOUTUBE_TITLE_LIMIT}."
+        )
+        .yellow()
+    );
+
+    let shortened = title_values.shortened(template, YOUTUBE_TITLE_LIMIT);
+    if yes
+        || Confirm::with_theme(&ColorfulTheme::default())
+            .with_prompt(format!("Would you like to use '{shortened}' instead?"))
+            .default(true)
+            .interact()
+            .map_err(Error::Prompt)?
+    {
+        println!("Okay, using '{shortened}' instead.");
+        Ok(shortened)
+    } else {
+        Ok(youtube_title)
+    }
+}
+
+// offer to fix common mistakes, `--yes` takes the fixes without asking
+fn checked_sermon_title(
+    mut sermon_title: String,
+    settings: &SermonTitles,
+    planned_preacher: Option<&str>,
+    yes: bool,
+) -> Result<String, Error> {
+    let mut preachers = settings.preachers.clone();
+    preachers.extend(planned_preacher.map(str::to_string));
+
+    loop {
+        let problems = check_sermon_title(&sermon_title, &preachers, settings.max_length);
+        if problems.is_empty() {
+            return Ok(sermon_title);
+        }
+        for problem in &problems {
+            println!("{}", format!("The sermon title {problem}.").yellow());
+        }
+
+        let corrected = corrected_sermon_title(&sermon_title);
+        if corrected != sermon_title
+            && (yes
+                || Confirm::with_theme(&ColorfulTheme::default())
+                    .with_prompt(format!("Would you like to use '{corrected}' instead?"))
+                    .default(true)
+                    .interact()
+                    .map_err(Error::Prompt)?)
+        {
+            println!("Okay, using '{corrected}' instead.");
+            sermon_title = corrected;
+            continue;
+        }
+
+        if yes
+            || !Confirm::with_theme(&ColorfulTheme::default())
+                .with_prompt("Would you like to change it?")
+                .default(false)
+                .interact()
+                .map_err(Error::Prompt)?
+        {
+            return Ok(sermon_title);
+        }
+        sermon_title = Input::with_theme(&ColorfulTheme::default())
+            .with_prompt("What should the sermon title be?")
+            .with_initial_text(sermon_title)
+            .allow_empty(true)
+            .interact_text()
+            .map_err(Error::Prompt)?;
+    }
+}
+
+// the setup, asking for everything that wasn't given on the command line
+fn publish(
+    args: &PublishArgs,
+    config: &Config,
+    config_location: Option<&Path>,
+    profile: Option<&str>,
+) -> Result<(), Error> {
+    print_setup_header(config, config_location, profile, args.dry_run);
+    recorded(config, args.dry_run, |history_entry| {
+        publish_steps(args, config, history_entry)
+    })
+}
+
+fn print_setup_header(
+    config: &Config,
+    config_location: Option<&Path>,
+    profile: Option<&str>,
+    dry_run: bool,
+) {
     // print version
     let version = env!("CARGO_PKG_VERSION");
     println!("Church Date Updater {version}");
 
-    let current_date = Local::now().date_naive();
+    if let Some(config_location) = config_location {
+        println!("Using settings from '{}'.", config_location.display());
+    }
+    if let Some(profile) = profile {
+        println!("Using the '{profile}' profile.");
+    }
+    if let Some(data_directory) = &config.paths.data_directory {
+        println!("Using files in '{}'.", data_directory.display());
+    }
 
-    let current_date_formatted = current_date.format("%m-%d-%y").to_string();
+    if dry_run {
+        println!("This is a dry run, nothing will be changed.");
+    }
+}
 
-    let current_month_day = current_date.day();
+// run the steps and save what they changed to the history
+fn recorded(
+    config: &Config,
+    dry_run: bool,
+    steps: impl FnOnce(&mut HistoryEntry) -> Result<(), Error>,
+) -> Result<(), Error> {
+    // filled in as each step changes something
+    let mut history_entry = HistoryEntry {
+        time: Local::now().naive_local(),
+        undoes: None,
+        target_date: None,
+        lower_third: None,
+        previous_lower_third: None,
+        color: None,
+        youtube: None,
+    };
+
+    let result = steps(&mut history_entry);
+
+    // saved even when a question couldn't be answered half way through, so undo
+    // knows what was already changed
+    if !dry_run && history_entry.changed_anything() {
+        if let Err(err) = append_history(&config.paths.history, &history_entry) {
+            println!("Unable to save this run to the history: {err}");
+        }
+    }
+    result
+}
 
-    let current_month_formatted = current_date.format("%B");
+fn publish_steps(
+    args: &PublishArgs,
+    config: &Config,
+    history_entry: &mut HistoryEntry,
+) -> Result<(), Error> {
+    let titles = &config.titles;
+    let paths = &config.paths;
+    let steps = &config.steps;
 
-    let ordinal_month_short = date_ordinal(current_month_day.into(), Short);
+    // the date of the service everything is prepared for
+    let target_date = match args.date {
+        Some(target_date) => target_date,
+        None if args.yes => Local::now().date_naive(),
+        None => ask_target_date(config.tradition)?,
+    };
+    history_entry.target_date = Some(target_date);
+
+    let church_date = next_church_date(target_date, Short, config.tradition)?;
+    let church_date_long = next_church_date(target_date, Long, config.tradition)?;
+
+    println!(
+        "Preparing for {}: {} ({}).",
+        target_date.format("%A, %B %-d, %Y"),
+        church_date.text,
+        church_date.color
+    );
+    if church_date.date != target_date {
+        println!(
+            "There isn't a church date on that day, so the next one is used ({}).",
+            church_date.date.format("%A, %B %-d")
+        );
+    }
+
+    // the first step that didn't work, the rest of the setup still runs
+    let mut failure = None;
+
+    // answers planned ahead are suggested instead of asking from scratch
+    let planned_service = match load_plan(&paths.plan) {
+        Ok(plan) => match plan.and_then(|plan| plan.find(&church_date, &church_date_long).cloned())
+        {
+            Some(planned_service) => {
+                println!("Using the plan from '{}'.", paths.plan.display());
+                planned_service
+            }
+            None => PlannedService::default(),
+        },
+        Err(err) => {
+            println!("{err}");
+            println!("You can still continue without the plan.");
+            PlannedService::default()
+        }
+    };
+
+    // the same for every title, the rest is filled in as it's answered
+    let mut title_values = TitleValues {
+        // the service's date, when preparing ahead of it
+        date: church_date.date,
+        locale: config.locale,
+        church_date: &church_date_long.text,
+        observance_short: &church_date.text,
+        observance_long: &church_date_long.text,
+        season: church_date.season,
+        color: church_date.color,
+        sermon_title: "",
+        typographic_quotes: config.sermon_titles.typographic_quotes,
+        preacher: planned_service.preacher.as_deref().unwrap_or_default(),
+        extra_texts: &[],
+    };
+
+    println!(
+        "Without a sermon title the YouTube title will be '{}'.",
+        title_values.fill(&titles.youtube)
+    );
 
     /*
      * OBS church date
      */
-    let obs_church_date = next_church_date(current_date, Short).text;
+    title_values.church_date = &church_date.text;
 
-    let mut obs_church_date_formatted =
-        format!("{current_month_formatted} {ordinal_month_short}, {obs_church_date}");
+    let mut obs_church_date_formatted = title_values.fill(&titles.lower_third_church_date);
 
     let mut obs_formatted_date_okay: bool = true;
-    if Confirm::with_theme(&ColorfulTheme::default())
+    if args.yes
+        || Confirm::with_theme(&ColorfulTheme::default())
         .with_prompt(format!("The church date used for OBS looks like this: '{obs_church_date_formatted}'. Does this look okay?"))
         .default(true)
         .interact()
-        .unwrap()
+        .map_err(Error::Prompt)?
     {
     } else {
         obs_formatted_date_okay = false;
@@ -49,111 +752,187 @@ fn main() {
             .with_prompt("What should it look like?")
             .with_initial_text(obs_church_date_formatted)
             .interact_text()
-            .unwrap();
+            .map_err(Error::Prompt)?;
     }
     println!("Okay, using '{obs_church_date_formatted}' for OBS.");
 
     // clone because we use it later
-    let mut obs_lower_list = vec![obs_church_date_formatted.clone()];
+    let mut lower_third = LowerThird::default();
+    lower_third.push(LineKind::ChurchDate, obs_church_date_formatted.clone());
 
     // if the liturgical date looks okay when it's generated for obs
     // generate another one with a slightly different format 'first' not '1st'
     // if it was changed by the user, use the date that the user set
-    let youtube_church_date = if obs_formatted_date_okay {
-        next_church_date(current_date, Long).text
+    title_values.church_date = if obs_formatted_date_okay {
+        &church_date_long.text
     } else {
-        obs_church_date_formatted
+        &obs_church_date_formatted
     };
 
-    let mut youtube_church_date_formatted;
-
     /*
      * Sermon title
      */
-    println!("Sermon titles have \"quotes\" added to them automatically.");
-    println!("The title should have already been carefully considered by the person who wrote the sermon.");
-    println!("Examples include: 'All Are Welcome' or '...And Peter'");
-    println!(
-        "Sermon titles are {}",
-        "not names, dates, or other information."
-            .bold()
-            .underline()
-            .red()
-    );
-    if Confirm::with_theme(&ColorfulTheme::default())
-        .with_prompt("Would you like to add a sermon title?")
-        .default(true)
-        .interact()
-        .unwrap()
-    {
-        let sermon_title: String = Input::with_theme(&ColorfulTheme::default())
-            .with_prompt("Okay, what is the sermon title?")
-            .interact_text()
-            .unwrap();
-        println!("Okay, using '{sermon_title}' for sermon title.");
-        youtube_church_date_formatted =
-            format!("\"{sermon_title}\" - {current_date_formatted} - {youtube_church_date}");
-        obs_lower_list.push(format!("\"{sermon_title}\""));
+    let sermon_title = if let Some(sermon_title) = &args.sermon_title {
+        Some(sermon_title.clone()).filter(|sermon_title| !sermon_title.is_empty())
+    } else if args.yes {
+        planned_service.sermon_title.clone()
     } else {
+        println!("Sermon titles have \"quotes\" added to them automatically.");
+        println!("The title should have already been carefully considered by the person who wrote the sermon.");
+        println!("Examples include: 'All Are Welcome' or '...And Peter'");
+        println!(
+            "Sermon titles are {}",
+            "not names, dates, or other information."
+                .bold()
+                .underline()
+                .red()
+        );
+        if Confirm::with_theme(&ColorfulTheme::default())
+            .with_prompt("Would you like to add a sermon title?")
+            .default(true)
+            .interact()
+            .map_err(Error::Prompt)?
+        {
+            let theme = ColorfulTheme::default();
+            let mut input = Input::with_theme(&theme);
+            input.with_prompt("Okay, what is the sermon title?");
+            if let Some(planned_sermon_title) = &planned_service.sermon_title {
+                input.with_initial_text(planned_sermon_title);
+            }
+            Some(input.interact_text().map_err(Error::Prompt)?)
+        } else {
+            None
+        }
+    };
+    let sermon_title = match sermon_title {
+        Some(sermon_title) => checked_sermon_title(
+            sermon_title,
+            &config.sermon_titles,
+            planned_service.preacher.as_deref(),
+            args.yes,
+        )?,
+        None => String::new(),
+    };
+    if sermon_title.is_empty() {
         println!("Okay, not using a sermon title.");
-        youtube_church_date_formatted = format!("{current_date_formatted} - {youtube_church_date}");
+    } else {
+        println!("Okay, using '{sermon_title}' for sermon title.");
+        lower_third.push(LineKind::SermonTitle, sermon_title.clone());
     }
 
     /*
      * Extra Text
      */
-    let mut extra_text_retry = true;
+    let mut extra_lower_texts = Vec::new();
+
+    // the command line replaces the plan
+    let given_extra_texts = if args.extra_text.is_empty() {
+        &planned_service.extra_text
+    } else {
+        &args.extra_text
+    };
+
+    for extra_lower_text in given_extra_texts {
+        println!("Okay, adding '{extra_lower_text}' to the OBS lower third.");
+        extra_lower_texts.push(extra_lower_text.clone());
+    }
 
-    let mut extra_text_count = 0;
+    // only ask when there wasn't any extra text on the command line
+    let mut extra_text_retry = !args.yes && args.extra_text.is_empty();
 
     while extra_text_retry {
         if Confirm::with_theme(&ColorfulTheme::default())
             .with_prompt("Would you like to add more text to the lower third for OBS?")
             .default(false)
             .interact()
-            .unwrap()
+            .map_err(Error::Prompt)?
         {
             let extra_lower_text = Input::with_theme(&ColorfulTheme::default())
                 .with_prompt("Okay, what else would you like to add?")
                 .interact_text()
-                .unwrap();
+                .map_err(Error::Prompt)?;
             println!("Okay, adding '{extra_lower_text}' to the OBS lower third.");
-
-            // add the first extra text to the title format
-            extra_text_count += 1;
-            if extra_text_count == 1 {
-                youtube_church_date_formatted =
-                    format!("{extra_lower_text} - {current_date_formatted} - {youtube_church_date}");
-            }
-
-            obs_lower_list.push(extra_lower_text);
+            extra_lower_texts.push(extra_lower_text);
         } else {
             println!("Okay, not adding extra text.");
             extra_text_retry = false;
         }
     }
 
-    //Write church text for OBS to file
-    #[allow(clippy::items_after_statements)]
-    fn write_lower_data(obs_lower_list: Vec<String>) -> Result<(), std::io::Error> {
-        let file = File::create("lower_data.txt")?;
-        let mut writer = BufWriter::new(file);
+    for extra_lower_text in extra_lower_texts {
+        lower_third.push(LineKind::ExtraText, extra_lower_text);
+    }
 
-        for item in obs_lower_list {
-            writeln!(writer, "{item}")?;
+    /*
+     * Lines added every week, and changing the lower third
+     */
+    let mut recurring_lines = match read_recurring_lines(&paths.recurring_lines) {
+        Ok(recurring_lines) => recurring_lines,
+        Err(err) => {
+            println!("{err}");
+            println!("You can still continue without the lines added every week.");
+            failure.get_or_insert(err);
+            Vec::new()
         }
+    };
+    for recurring_line in lower_third.add_recurring(&recurring_lines) {
+        println!("Okay, adding '{recurring_line}' to the OBS lower third, it's added every week.");
+    }
 
-        writer.flush()?;
-        Ok(())
+    if steps.lower_third && !args.yes {
+        let recurring_changed = edit_lower_third(
+            &mut lower_third,
+            &mut recurring_lines,
+            &config.lower_third.standard_lines,
+            |lower_third| lower_third.texts(&title_values, &titles.lower_third_sermon_title),
+            |sermon_title| {
+                checked_sermon_title(
+                    sermon_title,
+                    &config.sermon_titles,
+                    planned_service.preacher.as_deref(),
+                    false,
+                )
+            },
+        )?;
+        if recurring_changed {
+            if args.dry_run {
+                println!(
+                    "Would save the lines added every week to '{}'.",
+                    paths.recurring_lines.display()
+                );
+            } else if let Err(err) = write_recurring_lines(&paths.recurring_lines, &recurring_lines)
+            {
+                println!("Unable to save the lines added every week: {err}");
+                failure.get_or_insert(err);
+            }
+        }
     }
 
-    match write_lower_data(obs_lower_list) {
-        Ok(()) => println!("Successfully changed the OBS lower third."),
-        Err(err) => {
-            println!("Unable to change the OBS lower third: {err}");
-            println!("You can still continue with the rest of the setup.");
+    // a church date changed in the lower third is used for YouTube too
+    if let Some(church_date_line) = lower_third.find(LineKind::ChurchDate) {
+        if !obs_formatted_date_okay || church_date_line != obs_church_date_formatted {
+            title_values.church_date = church_date_line;
         }
     }
+    let extra_lower_texts = lower_third.extra_texts();
+    title_values.sermon_title = lower_third.find(LineKind::SermonTitle).unwrap_or_default();
+    title_values.extra_texts = &extra_lower_texts;
+    let youtube_church_date_formatted = title_values.fill(&titles.youtube);
+
+    let lower_third_data = lower_third.data(
+        &title_values,
+        &titles.lower_third_sermon_title,
+        &config.lower_third,
+    );
+    let obs_lower_list = lower_third_data.texts();
+
+    if !steps.lower_third {
+        println!("Not changing the OBS lower third, it's turned off in the settings.");
+    } else if let Err(err) =
+        publish_lower_third(config, &lower_third_data, args.dry_run, history_entry)
+    {
+        failure.get_or_insert(err);
+    }
 
     /*
      * OBS liturgical color
@@ -169,11 +948,9 @@ fn main() {
         "None".white(),
     ];
 
-    let possible_strings = &[
-        "White", "Green", "Purple", "Red", "Yellow", "Blue", "Black", "None",
-    ];
+    let possible_strings = &COLOR_NAMES;
 
-    let suggested_color = next_church_date(current_date, Short).color;
+    let suggested_color = church_date.color;
 
     let suggested_color_value = match suggested_color {
         Color::White => 0,
@@ -185,83 +962,557 @@ fn main() {
         Color::Black => 6,
     };
 
+    // the plan's color is suggested instead
+    let suggested_color_value = planned_service
+        .color
+        .as_ref()
+        .and_then(|color| {
+            possible_strings
+                .iter()
+                .position(|possible_string| possible_string == color)
+        })
+        .unwrap_or(suggested_color_value);
+
     let mut color_string = &possible_colors[suggested_color_value];
 
+    // the overlay uses the suggested color when the color step is off
     let mut color_string_regular = possible_strings[suggested_color_value];
 
-    if Confirm::with_theme(&ColorfulTheme::default())
-        .with_prompt(format!(
-            "The liturgical color used for OBS is: '{color_string}'. Does this look okay?"
-        ))
-        .default(true)
-        .interact()
-        .unwrap()
-    {
-        println!("Okay, using '{color_string}'.");
+    if !steps.color {
+        println!("Not changing the color for OBS, it's turned off in the settings.");
     } else {
-        let selection = Select::with_theme(&ColorfulTheme::default())
-            .with_prompt("What should it be?")
-            .default(suggested_color_value)
-            .items(&possible_colors[..])
-            .interact()
-            .unwrap();
-        color_string = &possible_colors[selection];
-        println!("Okay, using '{color_string}'.");
+        if let Some(color) = &args.color {
+            // already checked to be one of the color names when parsing the arguments
+            let selection = possible_strings
+                .iter()
+                .position(|possible_string| possible_string.eq_ignore_ascii_case(color))
+                .unwrap_or(suggested_color_value);
+            color_string = &possible_colors[selection];
+            println!("Okay, using '{color_string}'.");
+
+            color_string_regular = possible_strings[selection];
+        } else if args.yes
+            || Confirm::with_theme(&ColorfulTheme::default())
+                .with_prompt(format!(
+                    "The liturgical color used for OBS is: '{color_string}'. Does this look okay?"
+                ))
+                .default(true)
+                .interact()
+                .map_err(Error::Prompt)?
+        {
+            println!("Okay, using '{color_string}'.");
+        } else {
+            let selection = Select::with_theme(&ColorfulTheme::default())
+                .with_prompt("What should it be?")
+                .default(suggested_color_value)
+                .items(&possible_colors[..])
+                .interact()
+                .map_err(Error::Prompt)?;
+            color_string = &possible_colors[selection];
+            println!("Okay, using '{color_string}'.");
+
+            color_string_regular = possible_strings[selection];
+        }
 
-        color_string_regular = possible_strings[selection];
+        if let Err(err) = publish_color(paths, color_string_regular, args.dry_run, history_entry) {
+            failure.get_or_insert(err);
+        }
     }
 
-    let old_file = format!("pics/{color_string_regular}.png");
+    /*
+     * Overlay
+     */
+    if !steps.overlay {
+        println!("Not making the overlay, it's turned off in the settings.");
+    } else if let Err(err) = publish_overlay(config, color_string_regular, args.dry_run) {
+        failure.get_or_insert(err);
+    }
 
-    let new_file = "pics/Current_Color.png";
+    /*
+     * OBS sources, off unless OBS's WebSocket server is used
+     */
+    if steps.obs {
+        let lower_third = steps.lower_third.then_some(obs_lower_list.as_slice());
+        let color_name = steps.color.then_some(color_string_regular);
+        if let Err(err) = publish_obs(config, lower_third, color_name, args.dry_run) {
+            failure.get_or_insert(err);
+        }
+    }
+
+    /*
+     * Update YouTube api
+     */
+    if args.no_youtube {
+        println!("Okay, not updating the YouTube title.");
+    } else if !steps.youtube {
+        println!("Not updating the YouTube title, it's turned off in the settings.");
+    } else {
+        let youtube_church_date_formatted = fitted_youtube_title(
+            youtube_church_date_formatted,
+            &title_values,
+            &titles.youtube,
+            args.yes,
+        )?;
+        match update_youtube(args, paths, youtube_church_date_formatted) {
+            Ok(change) => history_entry.youtube = change,
+            Err(err @ Error::Prompt(_)) => return Err(err),
+            Err(err) => {
+                println!("Unable to update the YouTube title: {err}");
+                failure.get_or_insert(err);
+            }
+        }
+    }
+
+    failure.map_or(Ok(()), Err)
+}
+
+// everything is chosen on one screen, the steps run after it's closed so what
+// they say can be read
+#[cfg(feature = "tui")]
+fn publish_tui(
+    config: &Config,
+    config_location: Option<&Path>,
+    profile: Option<&str>,
+    date: Option<NaiveDate>,
+    dry_run: bool,
+) -> Result<(), Error> {
+    let paths = &config.paths;
+    let steps = &config.steps;
+
+    let (plan, mut message) = match load_plan(&paths.plan) {
+        Ok(plan) => (plan, None),
+        Err(err) => (None, Some(err)),
+    };
+    let recurring_lines = read_recurring_lines(&paths.recurring_lines).unwrap_or_else(|err| {
+        message = Some(err.to_string());
+        Vec::new()
+    });
+    let target_date = date.unwrap_or_else(|| Local::now().date_naive());
+    let Some(choices) = lib_tui::run(
+        config,
+        plan.as_ref(),
+        &recurring_lines,
+        target_date,
+        message,
+    )?
+    else {
+        println!("Okay, not publishing anything.");
+        return Ok(());
+    };
+
+    print_setup_header(config, config_location, profile, dry_run);
+    recorded(config, dry_run, |history_entry| {
+        history_entry.target_date = Some(choices.target_date);
+
+        // the first step that didn't work, the rest still runs
+        let mut failure = None;
+
+        if !steps.lower_third {
+            println!("Not changing the OBS lower third, it's turned off in the settings.");
+        } else if let Err(err) =
+            publish_lower_third(config, &choices.lower_third, dry_run, history_entry)
+        {
+            failure.get_or_insert(err);
+        }
+
+        if !steps.color {
+            println!("Not changing the color for OBS, it's turned off in the settings.");
+        } else if let Err(err) = publish_color(paths, choices.color, dry_run, history_entry) {
+            failure.get_or_insert(err);
+        }
 
-    //you need to remove the file first to have OBS know that something changed
-    match fs::remove_file(new_file) {
+        if !steps.overlay {
+            println!("Not making the overlay, it's turned off in the settings.");
+        } else if let Err(err) = publish_overlay(config, choices.color, dry_run) {
+            failure.get_or_insert(err);
+        }
+
+        if steps.obs {
+            let lower_third_texts = choices.lower_third.texts();
+            let lower_third = steps.lower_third.then_some(lower_third_texts.as_slice());
+            let color_name = steps.color.then_some(choices.color);
+            if let Err(err) = publish_obs(config, lower_third, color_name, dry_run) {
+                failure.get_or_insert(err);
+            }
+        }
+
+        if !steps.youtube {
+            println!("Not updating the YouTube title, it's turned off in the settings.");
+        } else {
+            match send_youtube_title(paths, choices.youtube_title, true, dry_run) {
+                Ok(change) => history_entry.youtube = change,
+                Err(err @ Error::Prompt(_)) => return Err(err),
+                Err(err) => {
+                    println!("Unable to update the YouTube title: {err}");
+                    failure.get_or_insert(err);
+                }
+            }
+        }
+
+        failure.map_or(Ok(()), Err)
+    })
+}
+
+#[cfg(not(feature = "tui"))]
+fn publish_tui(
+    _config: &Config,
+    _config_location: Option<&Path>,
+    _profile: Option<&str>,
+    _date: Option<NaiveDate>,
+    _dry_run: bool,
+) -> Result<(), Error> {
+    missing_feature("tui", "Not starting the full-screen setup.");
+    Ok(())
+}
+
+// OBS is told directly, for the setup, the full-screen setup and undo
+#[cfg(feature = "obs-websocket")]
+fn publish_obs(
+    config: &Config,
+    lower_third: Option<&[String]>,
+    color_name: Option<&str>,
+    dry_run: bool,
+) -> Result<(), Error> {
+    let obs = &config.obs;
+    let color_image = color_name.map(|color_name| {
+        config
+            .paths
+            .image_directory
+            .join(format!("{color_name}.png"))
+    });
+    if dry_run {
+        println!("Would update OBS at {}:{}:", obs.host, obs.port);
+        if let (Some(source), Some(_)) = (&obs.text_source, lower_third) {
+            println!("    '{source}' to the lower third");
+        }
+        if let (Some(source), Some(color_image)) = (&obs.image_source, &color_image) {
+            println!("    '{source}' to '{}'", color_image.display());
+        }
+        if let Some(source) = &obs.browser_source {
+            println!("    '{source}' refreshed");
+        }
+        return Ok(());
+    }
+
+    match update_obs(obs, lower_third, color_image.as_deref()) {
         Ok(()) => {
-            // Don't need to print anything
+            println!("Successfully updated the sources in OBS.");
+            Ok(())
         }
         Err(err) => {
-            println!("Unable to remove old color file for OBS: {err}");
-            println!("This could be because the file does not exist, setup will continue.");
+            println!("{err}");
+            println!("You can still continue with the rest of the setup.");
+            Err(err)
         }
     }
+}
+
+#[cfg(not(feature = "obs-websocket"))]
+fn publish_obs(
+    _config: &Config,
+    _lower_third: Option<&[String]>,
+    _color_name: Option<&str>,
+    _dry_run: bool,
+) -> Result<(), Error> {
+    missing_feature("obs-websocket", "Not updating the sources in OBS.");
+    Ok(())
+}
 
-    std::thread::sleep(std::time::Duration::from_millis(200)); //wait because some computers run it too fast, and OBS does not see the new file change
-    match fs::copy(old_file, new_file) {
-        Ok(_) => println!("Successfully changed color for OBS."),
+#[cfg(not(feature = "server"))]
+fn serve(_config: &Config, _port: u16) -> Result<(), Error> {
+    missing_feature("server", "Not starting the overlay server.");
+    Ok(())
+}
+
+// the lower third step, for the setup and the full-screen setup
+fn publish_lower_third(
+    config: &Config,
+    lower_third_data: &LowerThirdData,
+    dry_run: bool,
+    history_entry: &mut HistoryEntry,
+) -> Result<(), Error> {
+    let paths = &config.paths;
+    let obs_lower_list = lower_third_data.texts();
+    if dry_run {
+        println!(
+            "Would write '{}' and '{}':",
+            paths.lower_third.display(),
+            paths.lower_third_json.display()
+        );
+        for item in &obs_lower_list {
+            println!("    {item}");
+        }
+        return Ok(());
+    }
+
+    // kept for undo
+    let previous_lower_third = fs::read_to_string(&paths.lower_third)
+        .ok()
+        .map(|text| text.lines().map(String::from).collect());
+    match write_lower_data(
+        &paths.lower_third,
+        &paths.lower_third_json,
+        lower_third_data,
+    ) {
+        Ok(()) => {
+            println!("Successfully changed the OBS lower third.");
+            history_entry.previous_lower_third = previous_lower_third;
+            history_entry.lower_third = Some(obs_lower_list);
+            Ok(())
+        }
+        Err(err) => {
+            println!("Unable to change the OBS lower third: {err}");
+            println!("You can still continue with the rest of the setup.");
+            Err(err)
+        }
+    }
+}
+
+// replace the image OBS shows with a copy of the color's image
+fn publish_color(
+    paths: &Paths,
+    color_name: &str,
+    dry_run: bool,
+    history_entry: &mut HistoryEntry,
+) -> Result<(), Error> {
+    let old_file = paths.image_directory.join(format!("{color_name}.png"));
+    let new_file = &paths.color_image;
+
+    if dry_run {
+        println!(
+            "Would replace '{}' with a copy of '{}'.",
+            new_file.display(),
+            old_file.display()
+        );
+        if !old_file.is_file() {
+            println!("'{}' doesn't exist, so it would fail.", old_file.display());
+        }
+        return Ok(());
+    }
+
+    match copy_output(&old_file, new_file) {
+        Ok(()) => {
+            println!("Successfully changed color for OBS.");
+            history_entry.color = Some(color_name.to_string());
+            Ok(())
+        }
         Err(err) => {
             println!("Unable to change color for OBS: {err}");
             println!("You can still continue with the rest of the setup.");
+            Err(copy_error(&old_file, new_file, err))
+        }
+    }
+}
+
+// the overlay made again in the color, for the setup, the full-screen setup and
+// undo
+fn publish_overlay(config: &Config, color_name: &str, dry_run: bool) -> Result<(), Error> {
+    let path = overlay_path(&config.paths);
+    let layout = config.overlay.layout.name();
+    if dry_run {
+        println!("Would write '{}' ({layout}, {color_name}).", path.display());
+        return Ok(());
+    }
+
+    match write_overlay(config, color_name) {
+        Ok(true) => {
+            println!("Successfully made the overlay ({layout}, {color_name}).");
+            Ok(())
+        }
+        Ok(false) => {
+            println!(
+                "'{}' was changed by hand, so it isn't made again.",
+                path.display()
+            );
+            println!("Use `overlay_template` in the settings for an overlay of your own, or delete it to get the built-in one back.");
+            Ok(())
+        }
+        Err(err) => {
+            println!("Unable to make the overlay: {err}");
+            println!("You can still continue with the rest of the setup.");
+            Err(err)
         }
     }
+}
 
-    /*
-     * Update YouTube api
-     */
-    if Confirm::with_theme(&ColorfulTheme::default())
-        .with_prompt(format!("The title used for YouTube looks like this: '{youtube_church_date_formatted}'. Does this look okay?"))
-        .default(true)
-        .interact()
-        .unwrap()
+#[cfg(feature = "youtube")]
+fn update_youtube(
+    args: &PublishArgs,
+    paths: &Paths,
+    mut youtube_church_date_formatted: String,
+) -> Result<Option<TitleChange>, Error> {
+    if let Some(youtube_title) = &args.youtube_title {
+        youtube_church_date_formatted = youtube_title.clone();
+    } else if args.yes
+        || Confirm::with_theme(&ColorfulTheme::default())
+            .with_prompt(format!("The title used for YouTube looks like this: '{youtube_church_date_formatted}'. Does this look okay?"))
+            .default(true)
+            .interact()
+            .map_err(Error::Prompt)?
     {
-
     } else {
         youtube_church_date_formatted = Input::with_theme(&ColorfulTheme::default())
             .with_prompt("What should it look like?")
             .with_initial_text(youtube_church_date_formatted)
+            .validate_with(|youtube_title: &String| {
+                let length = youtube_title.chars().count();
+                if length <= YOUTUBE_TITLE_LIMIT {
+                    Ok(())
+                } else {
+                    Err(format!(
+                        "It's {length} characters long, YouTube only allows {YOUTUBE_TITLE_LIMIT}."
+                    ))
+                }
+            })
             .interact_text()
-            .unwrap();
+            .map_err(Error::Prompt)?;
     }
     println!("Okay, using '{youtube_church_date_formatted}'.");
 
+    // a scheduled task can't answer if it should look for the stream again
+    send_youtube_title(
+        paths,
+        youtube_church_date_formatted,
+        !args.yes,
+        args.dry_run,
+    )
+}
+
+#[cfg(feature = "youtube")]
+fn send_youtube_title(
+    paths: &Paths,
+    youtube_title: String,
+    ask_to_retry: bool,
+    dry_run: bool,
+) -> Result<Option<TitleChange>, Error> {
+    // YouTube would only answer with an error
+    let length = youtube_title.chars().count();
+    if length > YOUTUBE_TITLE_LIMIT {
+        println!(
+            "The YouTube title is {length} characters long, YouTube only allows {YOUTUBE_TITLE_LIMIT}."
+        );
+        println!("Not updating the YouTube title.");
+        return Ok(None);
+    }
+
+    // a broadcast that was just scheduled can take a moment to show up
+    let try_again = || {
+        Confirm::with_theme(&ColorfulTheme::default())
+            .with_prompt("Couldn't find any YouTube IDs. Try dismissing anything in the YouTube studio before trying again. Would you like to try again?")
+            .default(true)
+            .interact()
+            .map_err(Error::Prompt)
+    };
+
     //test for secret.json
-    if fs::metadata("secret.json").is_ok() {
-        update_youtube_title(youtube_church_date_formatted);
+    if fs::metadata(&paths.youtube_secret).is_ok() {
+        update_youtube_title(
+            youtube_title,
+            &paths.youtube_secret,
+            &paths.youtube_token_cache,
+            ask_to_retry.then_some(&try_again as &dyn Fn() -> Result<bool, Error>),
+            dry_run,
+        )
     } else {
-        println!("'secret.json' does not exist. Not updating YouTube title.");
+        println!(
+            "'{}' does not exist. Not updating YouTube title.",
+            paths.youtube_secret.display()
+        );
+        Ok(None)
     }
+}
 
-    //wait a little bit so they can read the text
-    let wait_time = std::time::Duration::from_secs(3);
-    std::thread::sleep(wait_time);
+#[cfg(not(feature = "youtube"))]
+fn update_youtube(
+    _args: &PublishArgs,
+    paths: &Paths,
+    youtube_church_date_formatted: String,
+) -> Result<Option<TitleChange>, Error> {
+    send_youtube_title(paths, youtube_church_date_formatted, false, false)
+}
+
+#[cfg(not(feature = "youtube"))]
+fn send_youtube_title(
+    _paths: &Paths,
+    youtube_title: String,
+    _ask_to_retry: bool,
+    _dry_run: bool,
+) -> Result<Option<TitleChange>, Error> {
+    println!("The title for YouTube would be: '{youtube_title}'.");
+    missing_feature("youtube", "Not updating the YouTube title.");
+    Ok(None)
+}
+
+#[cfg(feature = "youtube")]
+fn undo_youtube(paths: &Paths, youtube: &TitleChange) -> Result<Option<TitleChange>, Error> {
+    if fs::metadata(&paths.youtube_secret).is_err() {
+        println!(
+            "'{}' does not exist. Not restoring the YouTube title.",
+            paths.youtube_secret.display()
+        );
+        return Ok(None);
+    }
+    restore_youtube_title(
+        youtube.broadcast_id.clone(),
+        youtube.old_title.clone(),
+        &paths.youtube_secret,
+        &paths.youtube_token_cache,
+    )?;
+    Ok(Some(TitleChange {
+        broadcast_id: youtube.broadcast_id.clone(),
+        old_title: youtube.new_title.clone(),
+        new_title: youtube.old_title.clone(),
+    }))
+}
+
+#[cfg(not(feature = "youtube"))]
+fn undo_youtube(_paths: &Paths, _youtube: &TitleChange) -> Result<Option<TitleChange>, Error> {
+    missing_feature("youtube", "Not restoring the YouTube title.");
+    Ok(None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lib_test_directory::test_directory;
+
+    fn file_names(directory: &Path) -> Vec<String> {
+        let mut names: Vec<String> = fs::read_dir(directory)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+            .collect();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn dry_runs_write_nothing() {
+        let directory = test_directory("dry-run");
+        let config = Config {
+            paths: Paths::default().resolved(&directory),
+            ..Config::default()
+        };
+        fs::create_dir_all(&config.paths.image_directory).unwrap();
+        fs::write(config.paths.image_directory.join("Red.png"), "red").unwrap();
+        let mut history_entry = HistoryEntry {
+            time: Local::now().naive_local(),
+            undoes: None,
+            target_date: None,
+            lower_third: None,
+            previous_lower_third: None,
+            color: None,
+            youtube: None,
+        };
+
+        let lower_third_data = LowerThirdData::new(&["Welcome!".to_string()], &config.lower_third);
+        publish_lower_third(&config, &lower_third_data, true, &mut history_entry).unwrap();
+        publish_color(&config.paths, "Red", true, &mut history_entry).unwrap();
+        publish_overlay(&config, "Red", true).unwrap();
+
+        assert_eq!(file_names(&directory), ["pics"]);
+        assert_eq!(file_names(&config.paths.image_directory), ["Red.png"]);
+        assert!(!history_entry.changed_anything());
+
+        fs::remove_dir_all(&directory).unwrap();
+    }
 }